@@ -1,18 +1,41 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    coins, entry_point, to_binary, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, SubMsg, Uint128, Uint64, WasmMsg,
+    coins, entry_point, from_binary, to_binary, Addr, Api, BankMsg, Binary, Coin, Deps, DepsMut,
+    Env, Event, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    SubMsgResult, Uint128, Uint64, WasmMsg,
 };
-use cw1155::Cw1155ExecuteMsg;
-use cw2::set_contract_version;
-use cw20::Cw20ExecuteMsg;
-use cw721::Cw721ExecuteMsg;
+use cw1155::{Cw1155BatchReceiveMsg, Cw1155ExecuteMsg, Cw1155QueryMsg, Cw1155ReceiveMsg};
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, Cw721ReceiveMsg};
+use semver::Version;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::{
     error::PlaylinkAirdropErr,
-    helpers::{AirdropCampaign, Asset, AssetType, NATIVE_DENOM},
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{AirdropPlatform, AIRDROP_PLATFORM, ALL_CAMPAIGNS, OPERATORS},
+    events::{
+        campaign_event, campaign_release_event, config_event, event, fee_withdrawal_event,
+        transfer_event,
+    },
+    helpers::{
+        compress_pubkey, is_valid_merkle_root, merkle_leaf, verify_merkle_proof, voucher_digest,
+        AirdropCampaign, Asset, AssetType, CampaignStatus, Role, Voucher, NATIVE_DENOM,
+    },
+    migrations::{migrate_v0_2, migrate_v0_3, migrate_v0_4},
+    msg::{
+        AccountRolesResponse, CampaignSettings, CampaignsResponse, DelegatesResponse, ExecuteMsg,
+        FailedTransfersResponse, FeeAssetBalance, FeeBalancesResponse, InstantiateMsg, MigrateMsg,
+        PlatformConfigResponse, QueryMsg, ReceiveMsg, RoleMembersResponse, SimulateAirdropResponse,
+        SimulatedTransfer, TokenReceiveMsg, VestingAllocationsResponse, VestingStatus,
+        VoucherSignersResponse,
+    },
+    state::{
+        all_campaigns, AirdropBatch, AirdropPlatform, BatchEntry, FailedTransfer, FeeBalance,
+        PendingAdmin, VestingAllocation, AIRDROP_BATCHES, AIRDROP_PLATFORM, BATCH_COUNTER,
+        CAMPAIGN_DELEGATES, CAMPAIGN_INSTANCES, CAMPAIGN_RECIPIENTS, CLAIMED_LEAVES,
+        ESCROWED_NATIVE, ESCROW_DEPOSITS, FAILED_TRANSFERS, FEE_BALANCES, LEGACY_CLAIMS, PAUSED,
+        PENDING_ADMIN, ROLES, USED_NONCES, VESTING_ALLOCATIONS, VOUCHER_SIGNERS,
+    },
 };
 
 // version info for migration info
@@ -33,9 +56,10 @@ pub fn instantiate(
         });
     }
     let airdrop_platform = AirdropPlatform {
-        admin: info.sender.clone(),
-        max_match_size: msg.max_batch_size,
+        admin: Some(info.sender.clone()),
+        max_batch_size: msg.max_batch_size,
         fee_per_batch: msg.fee_per_batch,
+        fee_token: None,
     };
     AIRDROP_PLATFORM.save(deps.storage, &airdrop_platform)?;
 
@@ -54,60 +78,214 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, PlaylinkAirdropErr> {
     match msg {
-        ExecuteMsg::SetOperators {
-            operators,
-            is_operators,
-        } => execute::set_operators(deps, info, operators, is_operators),
+        ExecuteMsg::GrantRole { role, account } => {
+            execute::set_role(deps, info, role, account, true)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute::set_role(deps, info, role, account, false)
+        }
         ExecuteMsg::SetMaxBatchSize { new_size } => {
             execute::set_max_batch_size(deps, info, new_size)
         }
         ExecuteMsg::SetFeePerBatch { new_fee } => execute::set_fee_per_batch(deps, info, new_fee),
-        ExecuteMsg::CreateAirdropCampaign {
-            campaign_id,
-            assets,
-            starting_time,
-        } => execute::create_airdrop_campaign(deps, env, info, campaign_id, assets, starting_time),
-        ExecuteMsg::UpdateCampaign {
-            campaign_id,
-            assets,
-            starting_time,
-        } => execute::update_campaign(deps, env, info, campaign_id, assets, starting_time),
+        ExecuteMsg::SetFeeToken { fee_token } => execute::set_fee_token(deps, info, fee_token),
+        ExecuteMsg::CreateAirdropCampaign(settings) => execute::create_airdrop_campaign(
+            deps,
+            env,
+            info.sender,
+            execute::Payment {
+                funds: info.funds,
+                cw20: None,
+            },
+            settings,
+        ),
+        ExecuteMsg::UpdateCampaign(settings) => execute::update_campaign(
+            deps,
+            env,
+            info.sender,
+            execute::Payment {
+                funds: info.funds,
+                cw20: None,
+            },
+            settings,
+        ),
         ExecuteMsg::Airdrop {
             campaign_id,
             asset_indexes,
             recipients,
-        } => execute::airdrop(deps, env, info, campaign_id, asset_indexes, recipients),
+            amounts,
+        } => execute::airdrop(
+            deps,
+            env,
+            info,
+            campaign_id,
+            asset_indexes,
+            recipients,
+            amounts,
+        ),
         ExecuteMsg::WithdrawAirdropFee { recipient } => {
-            execute::withdraw_airdrop_fee(deps, env, info, recipient)
+            execute::withdraw_airdrop_fee(deps, info, recipient)
+        }
+        ExecuteMsg::Claim {
+            campaign_id,
+            asset_index,
+            amount,
+            proof,
+        } => execute::claim(deps, env, info, campaign_id, asset_index, amount, proof),
+        ExecuteMsg::ClaimWithVoucher {
+            voucher,
+            pubkey,
+            signature,
+        } => execute::claim_with_voucher(deps, env, voucher, pubkey, signature),
+        ExecuteMsg::RegisterVoucherSigner { pubkey } => {
+            execute::set_voucher_signer(deps, info, pubkey, true)
+        }
+        ExecuteMsg::RevokeVoucherSigner { pubkey } => {
+            execute::set_voucher_signer(deps, info, pubkey, false)
         }
+        ExecuteMsg::CancelCampaign { campaign_id } => {
+            execute::cancel_campaign(deps, env, info, campaign_id)
+        }
+        ExecuteMsg::WithdrawVested {
+            campaign_id,
+            asset_index,
+        } => execute::withdraw_vested(deps, env, info, campaign_id, asset_index),
+        ExecuteMsg::SetPaused { paused } => execute::set_paused(deps, info, paused),
+        ExecuteMsg::SetCampaignPaused {
+            campaign_id,
+            paused,
+        } => execute::set_campaign_paused(deps, info, campaign_id, paused),
+        ExecuteMsg::SetCampaignDelegates {
+            campaign_id,
+            delegates,
+            is_delegates,
+        } => execute::set_campaign_delegates(deps, info, campaign_id, delegates, is_delegates),
+        ExecuteMsg::CloseCampaign { campaign_id } => {
+            execute::close_campaign(deps, env, info, campaign_id)
+        }
+        ExecuteMsg::ProposeNewAdmin { new_admin, expiry } => {
+            execute::propose_new_admin(deps, env, info, new_admin, expiry)
+        }
+        ExecuteMsg::AcceptAdmin {} => execute::accept_admin(deps, env, info),
+        ExecuteMsg::RenounceAdmin {} => execute::renounce_admin(deps, info),
+        ExecuteMsg::Receive(TokenReceiveMsg::Cw20(msg)) => {
+            execute::receive_cw20(deps, env, info, msg)
+        }
+        ExecuteMsg::Receive(TokenReceiveMsg::Cw1155(msg)) => {
+            execute::receive_cw1155(deps, info, msg)
+        }
+        ExecuteMsg::ReceiveNft(msg) => execute::receive_cw721(deps, info, msg),
+        ExecuteMsg::BatchReceive(msg) => execute::batch_receive_cw1155(deps, info, msg),
     }
 }
 
 pub mod execute {
     use super::{query::estimate_airdrop_fee, *};
 
-    pub fn set_operators(
+    /// What a create or update call is paid with: bank coins attached to the call, or CW20
+    /// tokens received through `Send`.
+    pub struct Payment {
+        pub funds: Vec<Coin>,
+        pub cw20: Option<(Addr, Uint128)>,
+    }
+
+    pub fn propose_new_admin(
         deps: DepsMut,
+        env: Env,
         info: MessageInfo,
-        operators: Vec<String>,
-        is_operators: Vec<bool>,
+        new_admin: String,
+        expiry: Option<Uint64>,
     ) -> Result<Response, PlaylinkAirdropErr> {
-        if info.sender != AIRDROP_PLATFORM.load(deps.storage)?.admin {
-            return Err(PlaylinkAirdropErr::NotAdmin {
-                account: info.sender.into(),
-            });
-        }
-        if operators.len() != is_operators.len() {
-            return Err(PlaylinkAirdropErr::LengthMismatch {});
+        ensure_admin(deps.as_ref(), &info.sender)?;
+        if let Some(expiry) = expiry {
+            if env.block.time.seconds() >= expiry.u64() {
+                return Err(PlaylinkAirdropErr::LowExpiry {});
+            }
         }
-        for (i, new_operator) in operators.iter().enumerate() {
-            if *is_operators.get(i).unwrap() {
-                OPERATORS.save(deps.storage, deps.api.addr_validate(&new_operator)?, &true)?;
-            } else {
-                OPERATORS.save(deps.storage, deps.api.addr_validate(&new_operator)?, &false)?;
+        let new_admin = deps.api.addr_validate(&new_admin)?;
+        PENDING_ADMIN.save(
+            deps.storage,
+            &PendingAdmin {
+                new_admin: new_admin.clone(),
+                expiry,
+            },
+        )?;
+        Ok(Response::new()
+            .add_attribute("action", "propose_new_admin")
+            .add_attribute("new_admin", new_admin))
+    }
+
+    pub fn accept_admin(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        let pending_admin = match PENDING_ADMIN.may_load(deps.storage)? {
+            Some(pending_admin) if pending_admin.new_admin == info.sender => pending_admin,
+            _ => {
+                return Err(PlaylinkAirdropErr::NotPendingAdmin {
+                    account: info.sender.into(),
+                })
+            }
+        };
+        if let Some(expiry) = pending_admin.expiry {
+            if env.block.time.seconds() >= expiry.u64() {
+                return Err(PlaylinkAirdropErr::AdminProposalExpired {});
             }
         }
-        Ok(Response::new().add_attribute("action", "set_operators"))
+        let platform = AIRDROP_PLATFORM.update(
+            deps.storage,
+            |mut platform| -> Result<_, PlaylinkAirdropErr> {
+                platform.admin = Some(pending_admin.new_admin);
+                Ok(platform)
+            },
+        )?;
+        PENDING_ADMIN.remove(deps.storage);
+        Ok(Response::new()
+            .add_attribute("action", "accept_admin")
+            .add_attribute("admin", info.sender)
+            .add_event(config_event("accept_admin", &platform)))
+    }
+
+    pub fn renounce_admin(
+        deps: DepsMut,
+        info: MessageInfo,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_admin(deps.as_ref(), &info.sender)?;
+        let platform = AIRDROP_PLATFORM.update(
+            deps.storage,
+            |mut platform| -> Result<_, PlaylinkAirdropErr> {
+                platform.admin = None;
+                Ok(platform)
+            },
+        )?;
+        PENDING_ADMIN.remove(deps.storage);
+        Ok(Response::new()
+            .add_attribute("action", "renounce_admin")
+            .add_event(config_event("renounce_admin", &platform)))
+    }
+
+    pub fn set_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        role: Role,
+        account: String,
+        granted: bool,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_admin(deps.as_ref(), &info.sender)?;
+        let account = deps.api.addr_validate(&account)?;
+        if granted {
+            ROLES.save(deps.storage, (role.key(), account.clone()), &true)?;
+        } else {
+            ROLES.remove(deps.storage, (role.key(), account.clone()));
+        }
+
+        let action = if granted { "grant_role" } else { "revoke_role" };
+        Ok(Response::new().add_attribute("action", action).add_event(
+            event(action)
+                .add_attribute("role", role.key())
+                .add_attribute("account", account),
+        ))
     }
 
     pub fn set_max_batch_size(
@@ -115,22 +293,20 @@ pub mod execute {
         info: MessageInfo,
         new_size: Uint64,
     ) -> Result<Response, PlaylinkAirdropErr> {
-        if !OPERATORS.load(deps.storage, info.sender.clone())? {
-            return Err(PlaylinkAirdropErr::NotOperator {
-                account: info.sender.into(),
-            });
-        }
+        ensure_role(deps.as_ref(), &info.sender, Role::BatchSizeManager)?;
         if new_size.u64() == 0 {
             return Err(PlaylinkAirdropErr::InvalidMaxBatchSize { size: new_size });
         }
-        AIRDROP_PLATFORM.update(
+        let platform = AIRDROP_PLATFORM.update(
             deps.storage,
             |mut platform| -> Result<_, PlaylinkAirdropErr> {
-                platform.max_match_size = new_size;
+                platform.max_batch_size = new_size;
                 Ok(platform)
             },
         )?;
-        Ok(Response::new().add_attribute("action", "set_max_batch_size"))
+        Ok(Response::new()
+            .add_attribute("action", "set_max_batch_size")
+            .add_event(config_event("set_max_batch_size", &platform)))
     }
 
     pub fn set_fee_per_batch(
@@ -138,118 +314,188 @@ pub mod execute {
         info: MessageInfo,
         new_fee: Uint128,
     ) -> Result<Response, PlaylinkAirdropErr> {
-        if !OPERATORS.load(deps.storage, info.sender.clone())? {
-            return Err(PlaylinkAirdropErr::NotOperator {
-                account: info.sender.into(),
-            });
-        }
-        AIRDROP_PLATFORM.update(
+        ensure_role(deps.as_ref(), &info.sender, Role::FeeManager)?;
+        let platform = AIRDROP_PLATFORM.update(
             deps.storage,
             |mut platform| -> Result<_, PlaylinkAirdropErr> {
                 platform.fee_per_batch = new_fee;
                 Ok(platform)
             },
         )?;
-        Ok(Response::new().add_attribute("action", "set_fee_per_batch"))
+        Ok(Response::new()
+            .add_attribute("action", "set_fee_per_batch")
+            .add_event(config_event("set_fee_per_batch", &platform)))
+    }
+
+    pub fn set_fee_token(
+        deps: DepsMut,
+        info: MessageInfo,
+        fee_token: Option<String>,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_role(deps.as_ref(), &info.sender, Role::FeeManager)?;
+        let fee_token = match fee_token {
+            Some(fee_token) => {
+                let fee_token = deps.api.addr_validate(&fee_token)?;
+                // Make sure that this is a CW20 token
+                deps.querier.query_wasm_smart::<cw20::TokenInfoResponse>(
+                    fee_token.clone(),
+                    &cw20::Cw20QueryMsg::TokenInfo {},
+                )?;
+                Some(fee_token)
+            }
+            None => None,
+        };
+        let platform = AIRDROP_PLATFORM.update(
+            deps.storage,
+            |mut platform| -> Result<_, PlaylinkAirdropErr> {
+                platform.fee_token = fee_token.clone();
+                Ok(platform)
+            },
+        )?;
+        Ok(Response::new()
+            .add_attribute("action", "set_fee_token")
+            .add_attributes(fee_token.map(|fee_token| ("fee_token", fee_token)))
+            .add_event(config_event("set_fee_token", &platform)))
     }
 
     pub fn create_airdrop_campaign(
         deps: DepsMut,
         env: Env,
-        info: MessageInfo,
-        campaign_id: String,
-        assets: Vec<Asset>,
-        starting_time: Uint64,
+        sender: Addr,
+        payment: Payment,
+        settings: CampaignSettings,
     ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_not_paused(deps.as_ref())?;
+        let CampaignSettings {
+            campaign_id,
+            assets,
+            starting_time,
+            merkle_root,
+            escrow,
+            ending_time,
+            strict,
+            self_serve,
+            unique_recipients,
+        } = settings;
+        let escrow = escrow.unwrap_or(false);
+        let self_serve = self_serve.unwrap_or(false);
+        let unique_recipients = unique_recipients.unwrap_or(false);
+
         // Check if campaign exists
-        if ALL_CAMPAIGNS.has(deps.storage, campaign_id.clone()) {
+        if all_campaigns().has(deps.storage, campaign_id.clone()) {
             return Err(PlaylinkAirdropErr::CampaignAlreadyCreated { campaign_id });
         }
 
-        // Check payment
-        let airdrop_fee = estimate_airdrop_fee(deps.as_ref(), Uint64::from(assets.len() as u64))?;
-        let mut payment = Uint128::zero();
-        let mut messages: Vec<SubMsg> = vec![];
-        match info
-            .funds
-            .iter()
-            .find(|c| c.denom == String::from(NATIVE_DENOM))
-        {
-            Some(coin) => payment += coin.amount,
-            _ => (),
-        }
-        if payment < airdrop_fee {
-            return Err(PlaylinkAirdropErr::InsufficientAirdropFee {
-                fee: airdrop_fee,
-                denom: String::from(NATIVE_DENOM),
-            });
-        } else {
-            // Return excess
-            messages.push(SubMsg::new(BankMsg::Send {
-                to_address: info.sender.clone().into(),
-                amount: coins((payment - airdrop_fee).u128(), NATIVE_DENOM),
-            }));
-        }
-
         // Validate data
         if env.block.time.seconds() >= starting_time.u64() {
             return Err(PlaylinkAirdropErr::LowStartingTime {});
         }
-        for asset in assets.iter() {
-            if asset.clone().asset_type as u8 > 2 {
-                return Err(PlaylinkAirdropErr::InvalidAssetType {
-                    asset_type: asset.asset_type.clone(),
-                });
-            }
-            if asset.asset_type == AssetType::CW20 && asset.asset_id != String::from("") {
-                return Err(PlaylinkAirdropErr::InvalidAssetId {
-                    asset_id: asset.asset_id.clone(),
-                });
-            }
-            if asset.asset_type == AssetType::CW721 && asset.available_amount.u128() != 1 {
-                return Err(PlaylinkAirdropErr::InvalidAssetAmount {
-                    asset_amount: asset.available_amount,
+        validate_ending_time(starting_time, ending_time)?;
+        validate_assets(&assets)?;
+        if strict.unwrap_or(false) {
+            validate_fulfillable(deps.as_ref(), &env, &sender, &assets, escrow)?;
+        }
+
+        // Check payment, which covers the airdrop fee and native assets. The fee is paid in the
+        // platform fee token when the call comes with CW20 tokens.
+        let platform = AIRDROP_PLATFORM.load(deps.storage)?;
+        let airdrop_fee = estimate_airdrop_fee(deps.as_ref(), Uint64::from(assets.len() as u64))?;
+        let native_assets = native_totals(&assets);
+        let fee_token = match payment.cw20 {
+            Some(_) => platform.fee_token,
+            None => None,
+        };
+        let (native_fee, mut messages) =
+            settle_fee(&sender, payment.cw20, fee_token.as_ref(), airdrop_fee)?;
+        messages.extend(settle_payment(
+            &sender,
+            coin_balances(&payment.funds),
+            native_fee,
+            &native_assets,
+        )?);
+        collect_fee(deps.storage, &fee_asset(fee_token.as_ref()), airdrop_fee)?;
+        for (denom, amount) in native_assets.iter() {
+            lock_native(deps.storage, denom, *amount)?;
+        }
+        if let Some(root) = &merkle_root {
+            if !is_valid_merkle_root(root) {
+                return Err(PlaylinkAirdropErr::InvalidMerkleRoot {
+                    merkle_root: root.clone(),
                 });
             }
         }
 
         // Create new airdrop campaign
-        let max_batch_size = AIRDROP_PLATFORM.load(deps.storage)?.max_match_size;
-        ALL_CAMPAIGNS.save(
-            deps.storage,
-            campaign_id.clone(),
-            &AirdropCampaign {
-                campaign_id,
-                creator: info.sender,
-                assets: assets.clone(),
-                max_batch_size,
-                starting_time,
-                total_available_assets: assets.iter().map(|asset| asset.available_amount).sum(),
-                airdrop_fee,
-            },
-        )?;
+        let max_batch_size = platform.max_batch_size;
+        let instance = CAMPAIGN_INSTANCES
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            + 1;
+        CAMPAIGN_INSTANCES.save(deps.storage, &instance)?;
+        let campaign = AirdropCampaign {
+            campaign_id: campaign_id.clone(),
+            creator: sender,
+            assets: assets.clone(),
+            max_batch_size,
+            starting_time,
+            total_available_assets: assets.iter().map(|asset| asset.available_amount).sum(),
+            airdrop_fee,
+            merkle_root,
+            escrow,
+            funded: escrow && is_prefunded(&assets),
+            executed_batches: Uint64::zero(),
+            initial_assets: assets.iter().map(|asset| asset.available_amount).sum(),
+            earned_fee: Uint128::zero(),
+            ending_time,
+            fee_token,
+            paused: false,
+            self_serve,
+            unique_recipients,
+            instance: Uint64::from(instance),
+        };
+        all_campaigns().save(deps.storage, campaign_id, &campaign)?;
 
         Ok(Response::new()
             .add_attribute("action", "create_airdrop_campaign")
+            .add_event(campaign_event(
+                "create_airdrop_campaign",
+                &campaign,
+                &fee_asset(campaign.fee_token.as_ref()),
+            ))
             .add_submessages(messages))
     }
 
     pub fn update_campaign(
         deps: DepsMut,
         env: Env,
-        info: MessageInfo,
-        campaign_id: String,
-        assets: Vec<Asset>,
-        starting_time: Uint64,
+        sender: Addr,
+        payment: Payment,
+        settings: CampaignSettings,
     ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_not_paused(deps.as_ref())?;
+        let CampaignSettings {
+            campaign_id,
+            assets,
+            starting_time,
+            merkle_root,
+            escrow,
+            ending_time,
+            strict,
+            self_serve,
+            unique_recipients,
+        } = settings;
+        let escrow = escrow.unwrap_or(false);
+        let self_serve = self_serve.unwrap_or(false);
+        let unique_recipients = unique_recipients.unwrap_or(false);
+
         // Make sure that this campaign exists
-        if !ALL_CAMPAIGNS.has(deps.storage, campaign_id.clone()) {
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
             return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
         }
-        let campaign = ALL_CAMPAIGNS.load(deps.storage, campaign_id.clone())?;
+        let campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
 
         // Check campaign ownership
-        if campaign.creator != info.sender {
+        if campaign.creator != sender {
             return Err(PlaylinkAirdropErr::NotCampaignCreator {
                 campaign_creator: campaign.creator.into(),
             });
@@ -262,77 +508,110 @@ pub mod execute {
             });
         }
 
-        // Check payment
-        let new_airdrop_fee =
-            estimate_airdrop_fee(deps.as_ref(), Uint64::from(assets.len() as u64))?;
-        let mut payment = Uint128::zero();
-        let mut messages: Vec<SubMsg> = vec![];
-        match info
-            .funds
-            .iter()
-            .find(|c| c.denom == String::from(NATIVE_DENOM))
+        // Escrowed assets are matched against the declared assets, so those are frozen once the
+        // first deposit arrives
+        if ESCROW_DEPOSITS
+            .prefix(campaign_id.clone())
+            .range(deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_some()
         {
-            Some(coin) => payment += coin.amount,
-            _ => (),
-        }
-        if new_airdrop_fee > campaign.airdrop_fee {
-            if payment < new_airdrop_fee - campaign.airdrop_fee {
-                return Err(PlaylinkAirdropErr::InsufficientAirdropFee {
-                    fee: new_airdrop_fee - campaign.airdrop_fee,
-                    denom: String::from(NATIVE_DENOM),
-                });
-            } else {
-                // Return excess
-                messages.push(SubMsg::new(BankMsg::Send {
-                    to_address: info.sender.clone().into(),
-                    amount: coins(
-                        (payment + campaign.airdrop_fee - new_airdrop_fee).u128(),
-                        NATIVE_DENOM,
-                    ),
-                }));
-            }
+            return Err(PlaylinkAirdropErr::CampaignFundingStarted { campaign_id });
         }
 
         // Validate data
         if env.block.time.seconds() >= starting_time.u64() {
             return Err(PlaylinkAirdropErr::LowStartingTime {});
         }
-        for asset in assets.iter() {
-            if asset.clone().asset_type as u8 > 2 {
-                return Err(PlaylinkAirdropErr::InvalidAssetType {
-                    asset_type: asset.asset_type.clone(),
-                });
+        validate_ending_time(starting_time, ending_time)?;
+        validate_assets(&assets)?;
+        if strict.unwrap_or(false) {
+            validate_fulfillable(deps.as_ref(), &env, &sender, &assets, escrow)?;
+        }
+
+        // Check payment. Native assets already held for this campaign count towards the new ones
+        // and any surplus is returned.
+        let new_airdrop_fee =
+            estimate_airdrop_fee(deps.as_ref(), Uint64::from(assets.len() as u64))?;
+        let old_native_assets = native_totals(&campaign.assets);
+        let new_native_assets = native_totals(&assets);
+        let mut funds = coin_balances(&payment.funds);
+        for (denom, amount) in old_native_assets.iter() {
+            *funds.entry(denom.clone()).or_default() += *amount;
+            release_native(deps.storage, denom, *amount)?;
+        }
+
+        // A higher fee is charged for the difference and a lower one refunds it, in the asset
+        // the campaign paid its fee in. Fees the campaign already earned are kept, so only the
+        // unearned part is ever refunded.
+        let fee_asset = fee_asset(campaign.fee_token.as_ref());
+        let earned_fee = campaign.earned_fee.min(new_airdrop_fee);
+        let old_unearned_fee = unearned_fee(&campaign);
+        let new_unearned_fee = new_airdrop_fee - earned_fee;
+        let extra_fee = new_unearned_fee.saturating_sub(old_unearned_fee);
+        let overpaid_fee = old_unearned_fee.saturating_sub(new_unearned_fee);
+        let (native_fee, mut messages) = settle_fee(
+            &sender,
+            payment.cw20,
+            campaign.fee_token.as_ref(),
+            extra_fee,
+        )?;
+        match &campaign.fee_token {
+            Some(fee_token) if !overpaid_fee.is_zero() => {
+                messages.push(cw20_transfer_msg(fee_token, &sender, overpaid_fee)?);
             }
-            if asset.asset_type == AssetType::CW20 && asset.asset_id != "" {
-                return Err(PlaylinkAirdropErr::InvalidAssetId {
-                    asset_id: asset.asset_id.clone(),
-                });
+            Some(_) => {}
+            None => {
+                *funds.entry(String::from(NATIVE_DENOM)).or_default() += overpaid_fee;
             }
-            if asset.asset_type == AssetType::CW721 && asset.available_amount.u128() != 1 {
-                return Err(PlaylinkAirdropErr::InvalidAssetAmount {
-                    asset_amount: asset.available_amount,
+        }
+        messages.extend(settle_payment(
+            &sender,
+            funds,
+            native_fee,
+            &new_native_assets,
+        )?);
+        collect_fee(deps.storage, &fee_asset, extra_fee)?;
+        refund_fee(deps.storage, &fee_asset, overpaid_fee)?;
+        for (denom, amount) in new_native_assets.iter() {
+            lock_native(deps.storage, denom, *amount)?;
+        }
+        if let Some(root) = &merkle_root {
+            if !is_valid_merkle_root(root) {
+                return Err(PlaylinkAirdropErr::InvalidMerkleRoot {
+                    merkle_root: root.clone(),
                 });
             }
         }
 
         // Update campaign info
-        let max_batch_size = AIRDROP_PLATFORM.load(deps.storage)?.max_match_size;
-        ALL_CAMPAIGNS.save(
-            deps.storage,
-            campaign_id.clone(),
-            &AirdropCampaign {
-                campaign_id,
-                creator: info.sender,
-                assets: assets.clone(),
-                max_batch_size,
-                starting_time,
-                total_available_assets: assets.iter().map(|asset| asset.available_amount).sum(),
-                airdrop_fee: new_airdrop_fee,
-            },
-        )?;
+        let max_batch_size = AIRDROP_PLATFORM.load(deps.storage)?.max_batch_size;
+        let campaign = AirdropCampaign {
+            campaign_id: campaign_id.clone(),
+            creator: sender,
+            assets: assets.clone(),
+            max_batch_size,
+            starting_time,
+            total_available_assets: assets.iter().map(|asset| asset.available_amount).sum(),
+            airdrop_fee: new_airdrop_fee,
+            merkle_root,
+            escrow,
+            funded: escrow && is_prefunded(&assets),
+            executed_batches: Uint64::zero(),
+            initial_assets: assets.iter().map(|asset| asset.available_amount).sum(),
+            earned_fee,
+            ending_time,
+            fee_token: campaign.fee_token,
+            paused: campaign.paused,
+            self_serve,
+            unique_recipients,
+            instance: campaign.instance,
+        };
+        all_campaigns().save(deps.storage, campaign_id, &campaign)?;
 
         Ok(Response::new()
             .add_attribute("action", "update_campaign")
+            .add_event(campaign_event("update_campaign", &campaign, &fee_asset))
             .add_submessages(messages))
     }
 
@@ -343,131 +622,1712 @@ pub mod execute {
         campaign_id: String,
         asset_indexes: Vec<Uint64>,
         recipients: Vec<String>,
+        amounts: Option<Vec<Uint128>>,
     ) -> Result<Response, PlaylinkAirdropErr> {
-        // Only operators can airdrop
-        if !OPERATORS.load(deps.storage, info.sender.clone())? {
-            return Err(PlaylinkAirdropErr::NotOperator {
-                account: info.sender.into(),
+        // Only airdrop executors, delegates of this campaign and self-serve creators can airdrop
+        ensure_airdrop_executor(deps.as_ref(), &info.sender, &campaign_id)?;
+        let mut campaign = check_airdrop_batch(
+            deps.as_ref(),
+            &env,
+            campaign_id.clone(),
+            &asset_indexes,
+            &recipients,
+            amounts.as_ref(),
+        )?;
+
+        // Airdrop. Each transfer replies with its batch ID and entry index, so a failing entry
+        // is rolled back on its own instead of reverting the whole batch.
+        let batch_id = BATCH_COUNTER.may_load(deps.storage)?.unwrap_or_default() + 1;
+        BATCH_COUNTER.save(deps.storage, &batch_id)?;
+        let mut messages: Vec<SubMsg> = vec![];
+        let mut events: Vec<Event> = vec![];
+        let mut entries: Vec<BatchEntry> = vec![];
+        for (i, asset_index) in asset_indexes.iter().enumerate() {
+            let (recipient, amount) = check_airdrop_entry(
+                deps.api,
+                &campaign,
+                *asset_index,
+                &recipients[i],
+                amounts.as_ref().map(|amounts| amounts[i]),
+            )?;
+            record_recipient(deps.storage, &campaign, &recipient)?;
+            let key = (
+                campaign.instance.u64(),
+                recipient.clone(),
+                asset_index.u64(),
+            );
+            if let Some(mut failed) = FAILED_TRANSFERS.may_load(deps.storage, key.clone())? {
+                failed.amount = failed.amount.saturating_sub(amount);
+                if failed.amount.is_zero() {
+                    FAILED_TRANSFERS.remove(deps.storage, key);
+                } else {
+                    FAILED_TRANSFERS.save(deps.storage, key, &failed)?;
+                }
+            }
+            // Transfers are reported once their reply confirms them
+            match pay_out(
+                deps.storage,
+                &env,
+                &campaign,
+                *asset_index,
+                recipient.clone(),
+                amount,
+            )? {
+                Some(message) => messages.push(SubMsg::reply_always(
+                    message.msg,
+                    batch_reply_id(batch_id, i as u64),
+                )),
+                None => events.push(transfer_event(
+                    "airdrop",
+                    &campaign,
+                    *asset_index,
+                    &recipient,
+                    amount,
+                )),
+            }
+            entries.push(BatchEntry {
+                asset_index: *asset_index,
+                recipient,
+                amount,
             });
+            campaign.total_available_assets -= amount;
+            campaign.assets[asset_index.u64() as usize].available_amount -= amount;
         }
+        campaign.executed_batches += Uint64::one();
+
+        // Update status or remove. A campaign with transfers still to reply is kept until the
+        // last reply, as a failed transfer puts its amount back.
+        if messages.is_empty() {
+            save_or_remove_campaign(deps.storage, &mut campaign)?;
+        } else {
+            accrue_fee(deps.storage, &mut campaign)?;
+            all_campaigns().save(deps.storage, campaign_id.clone(), &campaign)?;
+            AIRDROP_BATCHES.save(
+                deps.storage,
+                batch_id,
+                &AirdropBatch {
+                    campaign,
+                    entries,
+                    pending_replies: messages.len() as u64,
+                },
+            )?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "airdrop")
+            .add_events(events)
+            .add_submessages(messages))
+    }
+
+    /// Runs the checks of `Airdrop` that apply to the whole batch and returns its campaign.
+    pub fn check_airdrop_batch(
+        deps: Deps,
+        env: &Env,
+        campaign_id: String,
+        asset_indexes: &[Uint64],
+        recipients: &[String],
+        amounts: Option<&Vec<Uint128>>,
+    ) -> Result<AirdropCampaign, PlaylinkAirdropErr> {
+        ensure_not_paused(deps)?;
 
         // Make sure that this campaign exists
-        if !ALL_CAMPAIGNS.has(deps.storage, campaign_id.clone()) {
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
             return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
         }
-        let mut campaign = ALL_CAMPAIGNS.load(deps.storage, campaign_id.clone())?;
+        let campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
 
         // Make sure that this campaign has started
         if env.block.time.seconds() < campaign.starting_time.u64() {
             return Err(PlaylinkAirdropErr::CampaignNotStarts { campaign_id });
         }
+        if has_ended(env, &campaign) {
+            return Err(PlaylinkAirdropErr::CampaignEnded { campaign_id });
+        }
+        if campaign.paused {
+            return Err(PlaylinkAirdropErr::CampaignPaused { campaign_id });
+        }
+
+        // Assets of a Merkle campaign are only paid out through claims
+        if campaign.merkle_root.is_some() {
+            return Err(PlaylinkAirdropErr::ClaimOnlyCampaign { campaign_id });
+        }
+
+        // Escrow campaigns pay out only after every asset has been deposited
+        if campaign.escrow && !campaign.funded {
+            return Err(PlaylinkAirdropErr::CampaignNotFunded { campaign_id });
+        }
 
         // Validate data
         if asset_indexes.len() != recipients.len() {
             return Err(PlaylinkAirdropErr::LengthMismatch {});
         }
+        if let Some(amounts) = amounts {
+            if amounts.len() != asset_indexes.len() {
+                return Err(PlaylinkAirdropErr::LengthMismatch {});
+            }
+        }
         if asset_indexes.len() as u64 > campaign.max_batch_size.u64() {
             return Err(PlaylinkAirdropErr::TooManyAssetsAirdropped {
                 num_assets: Uint64::from(asset_indexes.len() as u64),
             });
         }
+        Ok(campaign)
+    }
 
-        // Airdrop
-        let mut messages: Vec<SubMsg> = vec![];
-        for (i, asset_index) in asset_indexes.iter().enumerate() {
-            if asset_index.u64() as usize >= campaign.assets.len() {
-                return Err(PlaylinkAirdropErr::IndexOutOfBound {
-                    index: *asset_index,
-                });
+    /// Runs the checks of `Airdrop` on one entry and returns its recipient and amount. Amounts
+    /// are checked against `campaign` as left by the previous entries.
+    pub fn check_airdrop_entry(
+        api: &dyn Api,
+        campaign: &AirdropCampaign,
+        asset_index: Uint64,
+        recipient: &str,
+        amount: Option<Uint128>,
+    ) -> Result<(Addr, Uint128), PlaylinkAirdropErr> {
+        let asset = match campaign.assets.get(asset_index.u64() as usize) {
+            Some(asset) => asset,
+            None => {
+                return Err(PlaylinkAirdropErr::IndexOutOfBound { index: asset_index });
             }
-            let asset = campaign.assets.get_mut(asset_index.u64() as usize).unwrap();
-            let recipient = deps
-                .api
-                .addr_validate(recipients.get(i).unwrap().as_str())?
-                .into();
-            match asset.asset_type {
-                AssetType::CW20 => {
-                    let message = Cw20ExecuteMsg::TransferFrom {
-                        owner: campaign.creator.clone().into(),
-                        recipient,
-                        amount: Uint128::from(asset.available_amount),
-                    };
-                    let airdrop_msg = SubMsg::new(WasmMsg::Execute {
-                        contract_addr: asset.asset_address.clone().into(),
-                        msg: to_binary(&message)?,
-                        funds: vec![],
-                    });
-                    messages.push(airdrop_msg);
-                }
-                AssetType::CW721 => {
-                    let message = Cw721ExecuteMsg::TransferNft {
-                        recipient,
-                        token_id: asset.asset_id.clone(),
-                    };
-                    let airdrop_msg = SubMsg::new(WasmMsg::Execute {
-                        contract_addr: asset.asset_address.clone().into(),
-                        msg: to_binary(&message)?,
-                        funds: vec![],
-                    });
-                    messages.push(airdrop_msg);
-                }
-                AssetType::CW1155 => {
-                    let message = Cw1155ExecuteMsg::SendFrom {
-                        from: campaign.creator.clone().into(),
-                        to: recipient,
-                        token_id: asset.asset_id.clone(),
-                        value: Uint128::from(asset.available_amount),
-                        msg: None,
-                    };
-                    let airdrop_msg = SubMsg::new(WasmMsg::Execute {
-                        contract_addr: asset.asset_address.clone().into(),
-                        msg: to_binary(&message)?,
-                        funds: vec![],
-                    });
-                    messages.push(airdrop_msg);
+        };
+        let recipient = api.addr_validate(recipient)?;
+
+        // Without explicit amounts, the whole remaining amount of the asset is sent
+        let amount = amount.unwrap_or(asset.available_amount);
+        if amount.is_zero() {
+            return Err(PlaylinkAirdropErr::InvalidAssetAmount {
+                asset_amount: amount,
+            });
+        }
+        if amount > asset.available_amount {
+            return Err(PlaylinkAirdropErr::AssetAmountExceeded {
+                index: asset_index,
+                available: asset.available_amount,
+            });
+        }
+        Ok((recipient, amount))
+    }
+
+    /// Whether a Merkle leaf of the campaign has been claimed, including claims recorded by
+    /// recipient and asset index for campaigns created before 0.4.0.
+    pub fn is_leaf_claimed(
+        storage: &dyn Storage,
+        campaign: &AirdropCampaign,
+        recipient: &Addr,
+        asset_index: Uint64,
+        leaf: &[u8],
+    ) -> bool {
+        CLAIMED_LEAVES.has(storage, (campaign.instance.u64(), leaf))
+            || (campaign.instance.is_zero()
+                && LEGACY_CLAIMS.has(
+                    storage,
+                    (
+                        campaign.campaign_id.clone(),
+                        recipient.clone(),
+                        asset_index.u64(),
+                    ),
+                ))
+    }
+
+    /// Rejects wallets that already received from a campaign with `unique_recipients`, and
+    /// records them otherwise. Records are cleared when the campaign is removed.
+    fn record_recipient(
+        storage: &mut dyn Storage,
+        campaign: &AirdropCampaign,
+        recipient: &Addr,
+    ) -> Result<(), PlaylinkAirdropErr> {
+        if !campaign.unique_recipients {
+            return Ok(());
+        }
+        let key = (campaign.instance.u64(), recipient.clone());
+        if CAMPAIGN_RECIPIENTS.has(storage, key.clone()) {
+            return Err(PlaylinkAirdropErr::RecipientAlreadyReceived {
+                recipient: recipient.into(),
+            });
+        }
+        CAMPAIGN_RECIPIENTS.save(storage, key, &true)?;
+        Ok(())
+    }
+
+    /// Reply ID of an airdrop transfer: the batch ID in the upper 32 bits and the entry index
+    /// in the lower ones.
+    fn batch_reply_id(batch_id: u64, entry_index: u64) -> u64 {
+        (batch_id << 32) | entry_index
+    }
+
+    /// Handles the reply of an airdrop transfer, reporting it if it went through. Once every transfer of the batch has replied,
+    /// drops the batch and removes its campaign if nothing is left to pay out.
+    pub fn batch_reply(
+        mut deps: DepsMut,
+        id: u64,
+        result: SubMsgResult,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        let batch_id = id >> 32;
+        let mut batch = AIRDROP_BATCHES.load(deps.storage, batch_id)?;
+        let entry_index = id & u64::from(u32::MAX);
+        let response = match result {
+            SubMsgResult::Err(error) => failed_transfer(deps.branch(), &batch, entry_index, error)?,
+            SubMsgResult::Ok(_) => {
+                let entry = batch
+                    .entries
+                    .get(entry_index as usize)
+                    .ok_or_else(|| StdError::not_found("BatchEntry"))?;
+                Response::new().add_event(transfer_event(
+                    "airdrop",
+                    &batch.campaign,
+                    entry.asset_index,
+                    &entry.recipient,
+                    entry.amount,
+                ))
+            }
+        };
+        batch.pending_replies -= 1;
+        if batch.pending_replies == 0 {
+            AIRDROP_BATCHES.remove(deps.storage, batch_id);
+            let campaign_id = batch.campaign.campaign_id.clone();
+            if let Some(campaign) = all_campaigns().may_load(deps.storage, campaign_id)? {
+                if campaign.instance == batch.campaign.instance
+                    && campaign.total_available_assets.is_zero()
+                {
+                    remove_campaign(deps.storage, &campaign)?;
                 }
             }
-            campaign.total_available_assets -= asset.available_amount;
-            asset.available_amount = Uint128::zero();
+        } else {
+            AIRDROP_BATCHES.save(deps.storage, batch_id, &batch)?;
         }
+        Ok(response)
+    }
 
-        // Update status or remove
-        if campaign.total_available_assets.u128() > 0 {
-            ALL_CAMPAIGNS.save(deps.storage, campaign_id, &campaign)?;
-        } else {
-            ALL_CAMPAIGNS.remove(deps.storage, campaign_id);
+    /// Puts the amount of a failed airdrop transfer back into its campaign and records the
+    /// failure so the entry can be retried.
+    fn failed_transfer(
+        deps: DepsMut,
+        batch: &AirdropBatch,
+        entry_index: u64,
+        error: String,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        let entry = batch
+            .entries
+            .get(entry_index as usize)
+            .ok_or_else(|| StdError::not_found("BatchEntry"))?
+            .clone();
+        let campaign_id = batch.campaign.campaign_id.clone();
+        // A transfer calling back into this contract may have removed the campaign, which is
+        // brought back from its snapshot
+        let mut campaign = match all_campaigns().may_load(deps.storage, campaign_id.clone())? {
+            Some(campaign) => campaign,
+            None => batch.campaign.clone(),
+        };
+
+        // Undo the bookkeeping done when the transfer was dispatched
+        let asset = &mut campaign.assets[entry.asset_index.u64() as usize];
+        asset.available_amount += entry.amount;
+        campaign.total_available_assets += entry.amount;
+        if asset.vesting.is_some() {
+            let key = (
+                entry.recipient.clone(),
+                campaign_id.clone(),
+                entry.asset_index.u64(),
+            );
+            let mut allocation = VESTING_ALLOCATIONS.load(deps.storage, key.clone())?;
+            allocation.total_amount -= entry.amount;
+            if allocation.total_amount.is_zero() {
+                VESTING_ALLOCATIONS.remove(deps.storage, key);
+            } else {
+                VESTING_ALLOCATIONS.save(deps.storage, key, &allocation)?;
+            }
+        } else if asset.asset_type == AssetType::Native {
+            lock_native(deps.storage, &asset.asset_id, entry.amount)?;
+        }
+        if campaign.unique_recipients {
+            CAMPAIGN_RECIPIENTS.remove(
+                deps.storage,
+                (campaign.instance.u64(), entry.recipient.clone()),
+            );
         }
+        all_campaigns().save(deps.storage, campaign_id.clone(), &campaign)?;
+
+        let key = (
+            campaign.instance.u64(),
+            entry.recipient.clone(),
+            entry.asset_index.u64(),
+        );
+        let failed_amount = match FAILED_TRANSFERS.may_load(deps.storage, key.clone())? {
+            Some(failed) => failed.amount + entry.amount,
+            None => entry.amount,
+        };
+        FAILED_TRANSFERS.save(
+            deps.storage,
+            key,
+            &FailedTransfer {
+                asset_index: entry.asset_index,
+                recipient: entry.recipient.clone(),
+                amount: failed_amount,
+                error: error.clone(),
+            },
+        )?;
 
         Ok(Response::new()
-            .add_attribute("action", "airdrop")
-            .add_submessages(messages))
+            .add_attribute("action", "airdrop_transfer_failed")
+            .add_attribute("campaign_id", campaign_id)
+            .add_attribute("asset_index", entry.asset_index)
+            .add_attribute("recipient", entry.recipient.clone())
+            .add_attribute("error", error.clone())
+            .add_event(
+                transfer_event(
+                    "airdrop_transfer_failed",
+                    &campaign,
+                    entry.asset_index,
+                    &entry.recipient,
+                    entry.amount,
+                )
+                .add_attribute("error", error),
+            ))
     }
 
     pub fn withdraw_airdrop_fee(
         deps: DepsMut,
-        env: Env,
         info: MessageInfo,
         recipient: String,
     ) -> Result<Response, PlaylinkAirdropErr> {
-        if info.sender != AIRDROP_PLATFORM.load(deps.storage)?.admin {
-            return Err(PlaylinkAirdropErr::NotAdmin {
-                account: info.sender.into(),
-            });
-        }
-        let message = SubMsg::new(BankMsg::Send {
-            to_address: deps.api.addr_validate(recipient.as_str())?.into(),
-            amount: vec![deps
-                .querier
-                .query_balance(env.contract.address, String::from(NATIVE_DENOM))?],
-        });
-        Ok(Response::new()
-            .add_attribute("action", "withdraw_airdrop_fee")
-            .add_submessage(message))
-    }
-}
+        ensure_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+        let recipient = deps.api.addr_validate(recipient.as_str())?;
+
+        // Only earned fees are sent. Refundable fees and campaign assets stay in the contract.
+        let fee_balances = FEE_BALANCES
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut messages: Vec<SubMsg> = vec![];
+        let mut events: Vec<Event> = vec![];
+        for (fee_asset, mut balance) in fee_balances {
+            if balance.earned.is_zero() {
+                continue;
+            }
+            events.push(fee_withdrawal_event(&recipient, &fee_asset, balance.earned));
+            if fee_asset == NATIVE_DENOM {
+                messages.push(SubMsg::new(BankMsg::Send {
+                    to_address: recipient.clone().into(),
+                    amount: coins(balance.earned.u128(), NATIVE_DENOM),
+                }));
+            } else {
+                messages.push(cw20_transfer_msg(
+                    &Addr::unchecked(fee_asset.clone()),
+                    &recipient,
+                    balance.earned,
+                )?);
+            }
+            balance.earned = Uint128::zero();
+            FEE_BALANCES.save(deps.storage, fee_asset, &balance)?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "withdraw_airdrop_fee")
+            .add_events(events)
+            .add_submessages(messages))
+    }
+
+    pub fn claim(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        campaign_id: String,
+        asset_index: Uint64,
+        amount: Uint128,
+        proof: Vec<String>,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_not_paused(deps.as_ref())?;
+
+        // Make sure that this campaign exists and is claimable
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
+            return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
+        }
+        let mut campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
+        let merkle_root = match campaign.merkle_root.clone() {
+            Some(merkle_root) => merkle_root,
+            None => return Err(PlaylinkAirdropErr::NotMerkleCampaign { campaign_id }),
+        };
+
+        // Make sure that this campaign has started
+        if env.block.time.seconds() < campaign.starting_time.u64() {
+            return Err(PlaylinkAirdropErr::CampaignNotStarts { campaign_id });
+        }
+        if has_ended(&env, &campaign) {
+            return Err(PlaylinkAirdropErr::CampaignEnded { campaign_id });
+        }
+        if campaign.paused {
+            return Err(PlaylinkAirdropErr::CampaignPaused { campaign_id });
+        }
+        if campaign.escrow && !campaign.funded {
+            return Err(PlaylinkAirdropErr::CampaignNotFunded { campaign_id });
+        }
+
+        // Validate data
+        if asset_index.u64() as usize >= campaign.assets.len() {
+            return Err(PlaylinkAirdropErr::IndexOutOfBound { index: asset_index });
+        }
+        let leaf = merkle_leaf(info.sender.as_str(), asset_index, amount);
+        if is_leaf_claimed(deps.storage, &campaign, &info.sender, asset_index, &leaf) {
+            return Err(PlaylinkAirdropErr::AlreadyClaimed { index: asset_index });
+        }
+        if !verify_merkle_proof(leaf, &proof, &merkle_root) {
+            return Err(PlaylinkAirdropErr::InvalidMerkleProof {});
+        }
+        let asset = campaign.assets.get(asset_index.u64() as usize).unwrap();
+        if amount.is_zero() {
+            return Err(PlaylinkAirdropErr::InvalidAssetAmount {
+                asset_amount: amount,
+            });
+        }
+        if amount > asset.available_amount {
+            return Err(PlaylinkAirdropErr::AssetAmountExceeded {
+                index: asset_index,
+                available: asset.available_amount,
+            });
+        }
+
+        record_recipient(deps.storage, &campaign, &info.sender)?;
+
+        // Claim
+        let transfer = transfer_event("claim", &campaign, asset_index, &info.sender, amount);
+        let message = pay_out(
+            deps.storage,
+            &env,
+            &campaign,
+            asset_index,
+            info.sender.clone(),
+            amount,
+        )?;
+        campaign.assets[asset_index.u64() as usize].available_amount -= amount;
+        campaign.total_available_assets -= amount;
+        CLAIMED_LEAVES.save(deps.storage, (campaign.instance.u64(), &leaf), &true)?;
+
+        // Update status or remove
+        save_or_remove_campaign(deps.storage, &mut campaign)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "claim")
+            .add_event(transfer)
+            .add_submessages(message))
+    }
+
+    pub fn claim_with_voucher(
+        deps: DepsMut,
+        env: Env,
+        voucher: Voucher,
+        pubkey: Binary,
+        signature: Binary,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        // Make sure that the voucher was signed by a registered signer and not redeemed yet.
+        // Signers are stored compressed, so a voucher cannot be redeemed once per encoding.
+        let pubkey = match compress_pubkey(&pubkey) {
+            Some(pubkey) if VOUCHER_SIGNERS.has(deps.storage, &pubkey) => pubkey,
+            _ => return Err(PlaylinkAirdropErr::VoucherSignerNotRegistered {}),
+        };
+        let digest = voucher_digest(&env.contract.address, &voucher)?;
+        if !deps
+            .api
+            .secp256k1_verify(&digest, &signature, &pubkey)
+            .unwrap_or(false)
+        {
+            return Err(PlaylinkAirdropErr::InvalidVoucherSignature {});
+        }
+        if env.block.time.seconds() >= voucher.expiry.u64() {
+            return Err(PlaylinkAirdropErr::VoucherExpired {
+                nonce: voucher.nonce,
+            });
+        }
+        let nonce_key = (pubkey.as_slice(), voucher.nonce.u64());
+        if USED_NONCES.has(deps.storage, nonce_key) {
+            return Err(PlaylinkAirdropErr::VoucherAlreadyUsed {
+                nonce: voucher.nonce,
+            });
+        }
+
+        // A voucher is paid out like a single-entry airdrop batch
+        let mut campaign = check_airdrop_batch(
+            deps.as_ref(),
+            &env,
+            voucher.campaign_id.clone(),
+            &[voucher.asset_index],
+            std::slice::from_ref(&voucher.recipient),
+            Some(&vec![voucher.amount]),
+        )?;
+        let (recipient, amount) = check_airdrop_entry(
+            deps.api,
+            &campaign,
+            voucher.asset_index,
+            &voucher.recipient,
+            Some(voucher.amount),
+        )?;
+        record_recipient(deps.storage, &campaign, &recipient)?;
+        USED_NONCES.save(deps.storage, nonce_key, &true)?;
+
+        // Claim
+        let transfer = transfer_event(
+            "claim_with_voucher",
+            &campaign,
+            voucher.asset_index,
+            &recipient,
+            amount,
+        )
+        .add_attribute("nonce", voucher.nonce);
+        let message = pay_out(
+            deps.storage,
+            &env,
+            &campaign,
+            voucher.asset_index,
+            recipient,
+            amount,
+        )?;
+        campaign.assets[voucher.asset_index.u64() as usize].available_amount -= amount;
+        campaign.total_available_assets -= amount;
+
+        // Update status or remove
+        save_or_remove_campaign(deps.storage, &mut campaign)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "claim_with_voucher")
+            .add_event(transfer)
+            .add_submessages(message))
+    }
+
+    pub fn set_voucher_signer(
+        deps: DepsMut,
+        info: MessageInfo,
+        pubkey: Binary,
+        registered: bool,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_admin(deps.as_ref(), &info.sender)?;
+        // Compressed or uncompressed secp256k1 public key, stored compressed
+        let pubkey = Binary::from(
+            compress_pubkey(&pubkey).ok_or(PlaylinkAirdropErr::InvalidVoucherSigner {})?,
+        );
+        if registered {
+            VOUCHER_SIGNERS.save(deps.storage, &pubkey, &true)?;
+        } else {
+            VOUCHER_SIGNERS.remove(deps.storage, &pubkey);
+        }
+
+        let action = if registered {
+            "register_voucher_signer"
+        } else {
+            "revoke_voucher_signer"
+        };
+        Ok(Response::new()
+            .add_attribute("action", action)
+            .add_event(event(action).add_attribute("pubkey", pubkey.to_base64())))
+    }
+
+    pub fn cancel_campaign(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        campaign_id: String,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        // Make sure that this campaign exists
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
+            return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
+        }
+        let campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
+
+        // Check campaign ownership
+        if campaign.creator != info.sender {
+            return Err(PlaylinkAirdropErr::NotCampaignCreator {
+                campaign_creator: campaign.creator.into(),
+            });
+        }
+
+        // Refund the fee not earned yet, together with held assets
+        let refunded_fee = unearned_fee(&campaign);
+        let messages = release_campaign(deps.storage, &env, &campaign, refunded_fee)?;
+        let fee_denom = fee_asset(campaign.fee_token.as_ref());
+        remove_campaign(deps.storage, &campaign)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "cancel_campaign")
+            .add_event(campaign_release_event(
+                "cancel_campaign",
+                &campaign,
+                refunded_fee,
+                &fee_denom,
+            ))
+            .add_submessages(messages))
+    }
+
+    pub fn withdraw_vested(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        campaign_id: String,
+        asset_index: Uint64,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_not_paused(deps.as_ref())?;
+
+        let key = (info.sender.clone(), campaign_id, asset_index.u64());
+        let mut allocation = match VESTING_ALLOCATIONS.may_load(deps.storage, key.clone())? {
+            Some(allocation) => allocation,
+            None => return Err(PlaylinkAirdropErr::NothingToWithdraw {}),
+        };
+
+        // Only the unlocked part not withdrawn yet can be pulled
+        let withdrawable = allocation
+            .schedule
+            .vested_amount(allocation.total_amount, env.block.time.seconds())
+            - allocation.withdrawn_amount;
+        if withdrawable.is_zero() {
+            return Err(PlaylinkAirdropErr::NothingToWithdraw {});
+        }
+        allocation.withdrawn_amount += withdrawable;
+        VESTING_ALLOCATIONS.save(deps.storage, key, &allocation)?;
+
+        if allocation.asset_type == AssetType::Native {
+            release_native(deps.storage, &allocation.asset_id, withdrawable)?;
+        }
+        let message = transfer_msg(
+            &env.contract.address,
+            true,
+            &Asset {
+                asset_type: allocation.asset_type,
+                asset_address: allocation.asset_address,
+                asset_id: allocation.asset_id,
+                available_amount: withdrawable,
+                vesting: None,
+            },
+            info.sender.into(),
+            withdrawable,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "withdraw_vested")
+            .add_attribute("amount", withdrawable)
+            .add_submessage(message))
+    }
+
+    pub fn set_paused(
+        deps: DepsMut,
+        info: MessageInfo,
+        paused: bool,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        ensure_role(deps.as_ref(), &info.sender, Role::Pauser)?;
+        PAUSED.save(deps.storage, &paused)?;
+
+        let action = if paused { "pause" } else { "unpause" };
+        Ok(Response::new()
+            .add_attribute("action", action)
+            .add_event(event(action).add_attribute("sender", info.sender)))
+    }
+
+    pub fn set_campaign_paused(
+        deps: DepsMut,
+        info: MessageInfo,
+        campaign_id: String,
+        paused: bool,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        // Make sure that this campaign exists
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
+            return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
+        }
+        let mut campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
+
+        // Both the creator and pausers can pause a campaign
+        if campaign.creator != info.sender
+            && ensure_role(deps.as_ref(), &info.sender, Role::Pauser).is_err()
+        {
+            return Err(PlaylinkAirdropErr::NotCampaignCreator {
+                campaign_creator: campaign.creator.into(),
+            });
+        }
+        campaign.paused = paused;
+        all_campaigns().save(deps.storage, campaign_id.clone(), &campaign)?;
+
+        let action = if paused {
+            "pause_campaign"
+        } else {
+            "unpause_campaign"
+        };
+        Ok(Response::new().add_attribute("action", action).add_event(
+            event(action)
+                .add_attribute("campaign_id", campaign_id)
+                .add_attribute("sender", info.sender),
+        ))
+    }
+
+    pub fn set_campaign_delegates(
+        deps: DepsMut,
+        info: MessageInfo,
+        campaign_id: String,
+        delegates: Vec<String>,
+        is_delegates: Vec<bool>,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        // Make sure that this campaign exists
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
+            return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
+        }
+        let campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
+
+        // Check campaign ownership
+        if campaign.creator != info.sender {
+            return Err(PlaylinkAirdropErr::NotCampaignCreator {
+                campaign_creator: campaign.creator.into(),
+            });
+        }
+
+        if delegates.len() != is_delegates.len() {
+            return Err(PlaylinkAirdropErr::LengthMismatch {});
+        }
+        for (i, delegate) in delegates.iter().enumerate() {
+            let delegate = deps.api.addr_validate(delegate)?;
+            if *is_delegates.get(i).unwrap() {
+                CAMPAIGN_DELEGATES.save(
+                    deps.storage,
+                    (campaign.instance.u64(), delegate),
+                    &true,
+                )?;
+            } else {
+                CAMPAIGN_DELEGATES.remove(deps.storage, (campaign.instance.u64(), delegate));
+            }
+        }
+        Ok(Response::new()
+            .add_attribute("action", "set_campaign_delegates")
+            .add_attribute("campaign_id", campaign_id))
+    }
+
+    pub fn close_campaign(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        campaign_id: String,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        // Make sure that this campaign exists
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
+            return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
+        }
+        let campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
+
+        // Check campaign ownership
+        if campaign.creator != info.sender {
+            return Err(PlaylinkAirdropErr::NotCampaignCreator {
+                campaign_creator: campaign.creator.into(),
+            });
+        }
+
+        // Only campaigns past their ending time can be closed
+        if !has_ended(&env, &campaign) {
+            return Err(PlaylinkAirdropErr::CampaignNotEnded { campaign_id });
+        }
+
+        // Return the leftover assets and the fee not earned yet
+        let refunded_fee = unearned_fee(&campaign);
+        let messages = release_campaign(deps.storage, &env, &campaign, refunded_fee)?;
+        let fee_denom = fee_asset(campaign.fee_token.as_ref());
+        remove_campaign(deps.storage, &campaign)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "close_campaign")
+            .add_event(campaign_release_event(
+                "close_campaign",
+                &campaign,
+                refunded_fee,
+                &fee_denom,
+            ))
+            .add_submessages(messages))
+    }
+
+    pub fn receive_cw20(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw20ReceiveMsg,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        let sender = deps.api.addr_validate(&msg.sender)?;
+        let payment = Payment {
+            funds: vec![],
+            cw20: Some((info.sender.clone(), msg.amount)),
+        };
+        match from_binary(&msg.msg)? {
+            ReceiveMsg::FundCampaign { campaign_id } => fund_campaign(
+                deps,
+                campaign_id,
+                msg.sender,
+                AssetType::CW20,
+                info.sender,
+                vec![(String::from(""), msg.amount)],
+            ),
+            ReceiveMsg::CreateAirdropCampaign(settings) => {
+                create_airdrop_campaign(deps, env, sender, payment, settings)
+            }
+            ReceiveMsg::UpdateCampaign(settings) => {
+                update_campaign(deps, env, sender, payment, settings)
+            }
+        }
+    }
+
+    pub fn receive_cw721(
+        deps: DepsMut,
+        info: MessageInfo,
+        msg: Cw721ReceiveMsg,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        match from_binary(&msg.msg)? {
+            ReceiveMsg::FundCampaign { campaign_id } => fund_campaign(
+                deps,
+                campaign_id,
+                msg.sender,
+                AssetType::CW721,
+                info.sender,
+                vec![(msg.token_id, Uint128::one())],
+            ),
+            ReceiveMsg::CreateAirdropCampaign(_) | ReceiveMsg::UpdateCampaign(_) => {
+                Err(PlaylinkAirdropErr::InvalidFeeToken {
+                    token: info.sender.into(),
+                })
+            }
+        }
+    }
+
+    pub fn receive_cw1155(
+        deps: DepsMut,
+        info: MessageInfo,
+        msg: Cw1155ReceiveMsg,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        match from_binary(&msg.msg)? {
+            ReceiveMsg::FundCampaign { campaign_id } => fund_campaign(
+                deps,
+                campaign_id,
+                msg.from.unwrap_or(msg.operator),
+                AssetType::CW1155,
+                info.sender,
+                vec![(msg.token_id, msg.amount)],
+            ),
+            ReceiveMsg::CreateAirdropCampaign(_) | ReceiveMsg::UpdateCampaign(_) => {
+                Err(PlaylinkAirdropErr::InvalidFeeToken {
+                    token: info.sender.into(),
+                })
+            }
+        }
+    }
+
+    pub fn batch_receive_cw1155(
+        deps: DepsMut,
+        info: MessageInfo,
+        msg: Cw1155BatchReceiveMsg,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        match from_binary(&msg.msg)? {
+            ReceiveMsg::FundCampaign { campaign_id } => fund_campaign(
+                deps,
+                campaign_id,
+                msg.from.unwrap_or(msg.operator),
+                AssetType::CW1155,
+                info.sender,
+                msg.batch,
+            ),
+            ReceiveMsg::CreateAirdropCampaign(_) | ReceiveMsg::UpdateCampaign(_) => {
+                Err(PlaylinkAirdropErr::InvalidFeeToken {
+                    token: info.sender.into(),
+                })
+            }
+        }
+    }
+
+    /// Matches deposited tokens against the declared assets of an escrow campaign, filling
+    /// assets in order. The campaign is marked funded once every asset is fully deposited.
+    fn fund_campaign(
+        deps: DepsMut,
+        campaign_id: String,
+        depositor: String,
+        asset_type: AssetType,
+        asset_address: Addr,
+        deposits: Vec<(String, Uint128)>,
+    ) -> Result<Response, PlaylinkAirdropErr> {
+        // Make sure that this campaign exists and is waiting for deposits
+        if !all_campaigns().has(deps.storage, campaign_id.clone()) {
+            return Err(PlaylinkAirdropErr::CampaignNotExists { campaign_id });
+        }
+        let mut campaign = all_campaigns().load(deps.storage, campaign_id.clone())?;
+        if !campaign.escrow {
+            return Err(PlaylinkAirdropErr::NotEscrowCampaign { campaign_id });
+        }
+        if campaign.funded {
+            return Err(PlaylinkAirdropErr::CampaignAlreadyFunded { campaign_id });
+        }
+        if campaign.creator != depositor {
+            return Err(PlaylinkAirdropErr::NotCampaignCreator {
+                campaign_creator: campaign.creator.into(),
+            });
+        }
+
+        // Fill matching assets
+        for (asset_id, amount) in deposits.into_iter() {
+            let mut remaining = amount;
+            for (i, asset) in campaign.assets.iter().enumerate() {
+                if remaining.is_zero() {
+                    break;
+                }
+                if asset.asset_type != asset_type
+                    || asset.asset_address != asset_address
+                    || asset.asset_id != asset_id
+                {
+                    continue;
+                }
+                let key = (campaign_id.clone(), i as u64);
+                let deposited = ESCROW_DEPOSITS
+                    .may_load(deps.storage, key.clone())?
+                    .unwrap_or_default();
+                let filled = remaining.min(asset.available_amount - deposited);
+                if !filled.is_zero() {
+                    ESCROW_DEPOSITS.save(deps.storage, key, &(deposited + filled))?;
+                    remaining -= filled;
+                }
+            }
+            if !remaining.is_zero() {
+                return Err(PlaylinkAirdropErr::ExcessEscrowDeposit { excess: remaining });
+            }
+        }
+
+        // Update funding status
+        let mut funded = true;
+        for (i, asset) in campaign.assets.iter().enumerate() {
+            if asset.asset_type == AssetType::Native {
+                continue;
+            }
+            let deposited = ESCROW_DEPOSITS
+                .may_load(deps.storage, (campaign_id.clone(), i as u64))?
+                .unwrap_or_default();
+            if deposited < asset.available_amount {
+                funded = false;
+                break;
+            }
+        }
+        campaign.funded = funded;
+        all_campaigns().save(deps.storage, campaign_id, &campaign)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "fund_campaign")
+            .add_attribute("funded", funded.to_string()))
+    }
+
+    /// Escrow campaigns pay out from this contract, others straight from the creator.
+    pub fn asset_owner(env: &Env, campaign: &AirdropCampaign) -> Addr {
+        if campaign.escrow {
+            env.contract.address.clone()
+        } else {
+            campaign.creator.clone()
+        }
+    }
+
+    /// Sends `amount` of a campaign asset to `recipient`. Vesting assets are moved into this
+    /// contract instead and recorded as an allocation of the recipient.
+    fn pay_out(
+        storage: &mut dyn Storage,
+        env: &Env,
+        campaign: &AirdropCampaign,
+        asset_index: Uint64,
+        recipient: Addr,
+        amount: Uint128,
+    ) -> StdResult<Option<SubMsg>> {
+        let asset = &campaign.assets[asset_index.u64() as usize];
+        let owner = asset_owner(env, campaign);
+        let schedule = match &asset.vesting {
+            Some(schedule) => schedule.clone(),
+            None => {
+                if asset.asset_type == AssetType::Native {
+                    release_native(storage, &asset.asset_id, amount)?;
+                }
+                return transfer_msg(&owner, campaign.escrow, asset, recipient.into(), amount)
+                    .map(Some);
+            }
+        };
+
+        let key = (recipient, campaign.campaign_id.clone(), asset_index.u64());
+        let mut allocation = VESTING_ALLOCATIONS
+            .may_load(storage, key.clone())?
+            .unwrap_or(VestingAllocation {
+                asset_type: asset.asset_type.clone(),
+                asset_address: asset.asset_address.clone(),
+                asset_id: asset.asset_id.clone(),
+                schedule,
+                total_amount: Uint128::zero(),
+                withdrawn_amount: Uint128::zero(),
+            });
+        allocation.total_amount += amount;
+        VESTING_ALLOCATIONS.save(storage, key, &allocation)?;
+
+        // Escrowed and native assets are already held by this contract
+        if campaign.escrow || asset.asset_type == AssetType::Native {
+            return Ok(None);
+        }
+        transfer_msg(
+            &owner,
+            false,
+            asset,
+            env.contract.address.clone().into(),
+            amount,
+        )
+        .map(Some)
+    }
+
+    fn ensure_admin(deps: Deps, account: &Addr) -> Result<(), PlaylinkAirdropErr> {
+        if AIRDROP_PLATFORM.load(deps.storage)?.admin.as_ref() != Some(account) {
+            return Err(PlaylinkAirdropErr::NotAdmin {
+                account: account.into(),
+            });
+        }
+        Ok(())
+    }
+
+    fn ensure_not_paused(deps: Deps) -> Result<(), PlaylinkAirdropErr> {
+        if PAUSED.may_load(deps.storage)?.unwrap_or(false) {
+            return Err(PlaylinkAirdropErr::PlatformPaused {});
+        }
+        Ok(())
+    }
+
+    /// Accepts the admin and accounts granted `role`.
+    fn ensure_role(deps: Deps, account: &Addr, role: Role) -> Result<(), PlaylinkAirdropErr> {
+        if AIRDROP_PLATFORM.load(deps.storage)?.admin.as_ref() == Some(account)
+            || ROLES.has(deps.storage, (role.key(), account.clone()))
+        {
+            return Ok(());
+        }
+        Err(PlaylinkAirdropErr::MissingRole {
+            account: account.into(),
+            role,
+        })
+    }
+
+    /// Accepts airdrop executors, the delegates of `campaign_id` and the creator of a
+    /// self-serve campaign.
+    fn ensure_airdrop_executor(
+        deps: Deps,
+        account: &Addr,
+        campaign_id: &str,
+    ) -> Result<(), PlaylinkAirdropErr> {
+        if let Some(campaign) = all_campaigns().may_load(deps.storage, String::from(campaign_id))? {
+            if CAMPAIGN_DELEGATES.has(deps.storage, (campaign.instance.u64(), account.clone()))
+                || (campaign.self_serve && campaign.creator == *account)
+            {
+                return Ok(());
+            }
+        }
+        ensure_role(deps, account, Role::AirdropExecutor)
+    }
+
+    /// Earns the fee due for what the campaign has paid out, after its assets changed.
+    fn save_or_remove_campaign(
+        storage: &mut dyn Storage,
+        campaign: &mut AirdropCampaign,
+    ) -> StdResult<()> {
+        accrue_fee(storage, campaign)?;
+        if campaign.total_available_assets.u128() > 0 {
+            all_campaigns().save(storage, campaign.campaign_id.clone(), campaign)
+        } else {
+            remove_campaign(storage, campaign)
+        }
+    }
+
+    fn remove_campaign(storage: &mut dyn Storage, campaign: &AirdropCampaign) -> StdResult<()> {
+        all_campaigns().remove(storage, campaign.campaign_id.clone())?;
+        for i in 0..campaign.assets.len() {
+            ESCROW_DEPOSITS.remove(storage, (campaign.campaign_id.clone(), i as u64));
+        }
+        Ok(())
+    }
+
+    /// Part of the airdrop fee the platform is owed for what the campaign has paid out: the
+    /// larger of the share of paid batches executed and the share of assets distributed, so
+    /// claims earn the fee like batches do. The fee covers one batch per `max_batch_size`
+    /// assets, and an exhausted campaign owes all of it.
+    pub fn due_fee(campaign: &AirdropCampaign) -> Uint128 {
+        if campaign.total_available_assets.is_zero() {
+            return campaign.airdrop_fee;
+        }
+        let paid_batches = (campaign.assets.len() as u64).div_ceil(campaign.max_batch_size.u64());
+        let batches_fee = if paid_batches == 0 {
+            Uint128::zero()
+        } else {
+            let executed_batches = campaign.executed_batches.u64().min(paid_batches);
+            campaign
+                .airdrop_fee
+                .multiply_ratio(executed_batches, paid_batches)
+        };
+        // Campaigns created before 0.4.0 have no `initial_assets`
+        let distributed_fee = if campaign.initial_assets.is_zero() {
+            Uint128::zero()
+        } else {
+            campaign.airdrop_fee.multiply_ratio(
+                campaign
+                    .initial_assets
+                    .saturating_sub(campaign.total_available_assets),
+                campaign.initial_assets,
+            )
+        };
+        batches_fee.max(distributed_fee)
+    }
+
+    /// Part of the airdrop fee not earned yet, refunded when the campaign is cancelled or closed.
+    pub fn unearned_fee(campaign: &AirdropCampaign) -> Uint128 {
+        campaign.airdrop_fee.saturating_sub(campaign.earned_fee)
+    }
+
+    /// Earns the part of `due_fee` above `earned_fee`. Earned fees are never given back, also
+    /// when a failed transfer returns assets, since they may have been withdrawn already.
+    fn accrue_fee(storage: &mut dyn Storage, campaign: &mut AirdropCampaign) -> StdResult<()> {
+        let due = due_fee(campaign);
+        if due > campaign.earned_fee {
+            earn_fee(
+                storage,
+                &fee_asset(campaign.fee_token.as_ref()),
+                due - campaign.earned_fee,
+            )?;
+            campaign.earned_fee = due;
+        }
+        Ok(())
+    }
+
+    /// Whether `operator` holds an unexpired `ApproveAll` over the CW721 tokens of `owner`.
+    fn is_cw721_operator(
+        deps: Deps,
+        env: &Env,
+        token: &Addr,
+        owner: &Addr,
+        operator: &str,
+    ) -> StdResult<bool> {
+        let mut start_after: Option<String> = None;
+        loop {
+            let response: cw721::OperatorsResponse = deps.querier.query_wasm_smart(
+                token,
+                &Cw721QueryMsg::AllOperators {
+                    owner: owner.into(),
+                    include_expired: Some(true),
+                    start_after: start_after.clone(),
+                    limit: None,
+                },
+            )?;
+            if response.operators.iter().any(|approval| {
+                approval.spender == operator && !approval.expires.is_expired(&env.block)
+            }) {
+                return Ok(true);
+            }
+            match response.operators.last() {
+                Some(last) => start_after = Some(last.spender.clone()),
+                None => return Ok(false),
+            }
+        }
+    }
+
+    /// Builds the messages returning `fee` and every asset this contract still holds for the
+    /// campaign to its creator.
+    fn release_campaign(
+        storage: &mut dyn Storage,
+        env: &Env,
+        campaign: &AirdropCampaign,
+        fee: Uint128,
+    ) -> StdResult<Vec<SubMsg>> {
+        let mut native_refund: BTreeMap<String, Uint128> = BTreeMap::new();
+        let mut messages: Vec<SubMsg> = vec![];
+        refund_fee(storage, &fee_asset(campaign.fee_token.as_ref()), fee)?;
+        match &campaign.fee_token {
+            Some(fee_token) if !fee.is_zero() => {
+                messages.push(cw20_transfer_msg(fee_token, &campaign.creator, fee)?);
+            }
+            Some(_) => {}
+            None => {
+                native_refund.insert(String::from(NATIVE_DENOM), fee);
+            }
+        }
+        for (i, asset) in campaign.assets.iter().enumerate() {
+            let held = match asset.asset_type {
+                AssetType::Native => {
+                    release_native(storage, &asset.asset_id, asset.available_amount)?;
+                    *native_refund.entry(asset.asset_id.clone()).or_default() +=
+                        asset.available_amount;
+                    continue;
+                }
+                _ if !campaign.escrow => continue,
+                // Nothing has been paid out before funding completes
+                _ if campaign.funded => asset.available_amount,
+                _ => ESCROW_DEPOSITS
+                    .may_load(storage, (campaign.campaign_id.clone(), i as u64))?
+                    .unwrap_or_default(),
+            };
+            if !held.is_zero() {
+                messages.push(transfer_msg(
+                    &env.contract.address,
+                    true,
+                    asset,
+                    campaign.creator.clone().into(),
+                    held,
+                )?);
+            }
+        }
+        let native_refund: Vec<Coin> = native_refund
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(denom, amount)| Coin { denom, amount })
+            .collect();
+        if !native_refund.is_empty() {
+            messages.insert(
+                0,
+                SubMsg::new(BankMsg::Send {
+                    to_address: campaign.creator.clone().into(),
+                    amount: native_refund,
+                }),
+            );
+        }
+        Ok(messages)
+    }
+
+    fn validate_assets(assets: &[Asset]) -> Result<(), PlaylinkAirdropErr> {
+        for asset in assets.iter() {
+            if asset.clone().asset_type as u8 > 3 {
+                return Err(PlaylinkAirdropErr::InvalidAssetType {
+                    asset_type: asset.asset_type.clone(),
+                });
+            }
+            if asset.asset_type == AssetType::CW20 && !asset.asset_id.is_empty() {
+                return Err(PlaylinkAirdropErr::InvalidAssetId {
+                    asset_id: asset.asset_id.clone(),
+                });
+            }
+            if asset.asset_type == AssetType::CW721 && asset.available_amount.u128() != 1 {
+                return Err(PlaylinkAirdropErr::InvalidAssetAmount {
+                    asset_amount: asset.available_amount,
+                });
+            }
+            if let Some(schedule) = &asset.vesting {
+                if asset.asset_type == AssetType::CW721 {
+                    return Err(PlaylinkAirdropErr::VestingNotSupported {
+                        asset_type: asset.asset_type.clone(),
+                    });
+                }
+                if !schedule.is_valid() {
+                    return Err(PlaylinkAirdropErr::InvalidVestingSchedule {});
+                }
+            }
+            if asset.asset_type == AssetType::Native {
+                if asset.asset_address.as_str() != "" {
+                    return Err(PlaylinkAirdropErr::InvalidAssetAddress {
+                        asset_address: asset.asset_address.clone().into(),
+                    });
+                }
+                if asset.asset_id.is_empty() {
+                    return Err(PlaylinkAirdropErr::InvalidAssetId {
+                        asset_id: asset.asset_id.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `creator` holds every asset of a campaign and, unless the assets are
+    /// deposited in escrow, has allowed this contract to move them.
+    fn validate_fulfillable(
+        deps: Deps,
+        env: &Env,
+        creator: &Addr,
+        assets: &[Asset],
+        escrow: bool,
+    ) -> Result<(), PlaylinkAirdropErr> {
+        // Assets of the same token add up against the balance and allowance of the creator
+        let mut totals: BTreeMap<(Addr, String), Uint128> = BTreeMap::new();
+        for (i, asset) in assets.iter().enumerate() {
+            let total = totals
+                .entry((asset.asset_address.clone(), asset.asset_id.clone()))
+                .or_default();
+            *total += asset.available_amount;
+            check_asset_transfer(
+                deps,
+                env,
+                asset,
+                Uint64::from(i as u64),
+                creator,
+                !escrow,
+                *total,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Checks that `owner` holds `amount` of an asset and, with `approval`, has allowed this
+    /// contract to move it. Native assets are paid upfront and always pass.
+    pub fn check_asset_transfer(
+        deps: Deps,
+        env: &Env,
+        asset: &Asset,
+        asset_index: Uint64,
+        owner: &Addr,
+        approval: bool,
+        amount: Uint128,
+    ) -> Result<(), PlaylinkAirdropErr> {
+        let query_failed = |err: StdError| PlaylinkAirdropErr::AssetQueryFailed {
+            index: asset_index,
+            error: err.to_string(),
+        };
+        let spender = env.contract.address.to_string();
+        match asset.asset_type {
+            AssetType::Native => {}
+            AssetType::CW20 => {
+                let balance: cw20::BalanceResponse = deps
+                    .querier
+                    .query_wasm_smart(
+                        &asset.asset_address,
+                        &Cw20QueryMsg::Balance {
+                            address: owner.into(),
+                        },
+                    )
+                    .map_err(query_failed)?;
+                if balance.balance < amount {
+                    return Err(PlaylinkAirdropErr::InsufficientAssetBalance {
+                        index: asset_index,
+                        balance: balance.balance,
+                    });
+                }
+                if !approval {
+                    return Ok(());
+                }
+                let allowance: cw20::AllowanceResponse = deps
+                    .querier
+                    .query_wasm_smart(
+                        &asset.asset_address,
+                        &Cw20QueryMsg::Allowance {
+                            owner: owner.into(),
+                            spender,
+                        },
+                    )
+                    .map_err(query_failed)?;
+                let allowance = if allowance.expires.is_expired(&env.block) {
+                    Uint128::zero()
+                } else {
+                    allowance.allowance
+                };
+                if allowance < amount {
+                    return Err(PlaylinkAirdropErr::InsufficientAllowance {
+                        index: asset_index,
+                        allowance,
+                    });
+                }
+            }
+            AssetType::CW721 => {
+                let owner_of: cw721::OwnerOfResponse = deps
+                    .querier
+                    .query_wasm_smart(
+                        &asset.asset_address,
+                        &Cw721QueryMsg::OwnerOf {
+                            token_id: asset.asset_id.clone(),
+                            include_expired: None,
+                        },
+                    )
+                    .map_err(query_failed)?;
+                if owner_of.owner != *owner {
+                    return Err(PlaylinkAirdropErr::AssetNotOwned {
+                        index: asset_index,
+                        owner: owner.into(),
+                    });
+                }
+                if amount > Uint128::one() {
+                    return Err(PlaylinkAirdropErr::InsufficientAssetBalance {
+                        index: asset_index,
+                        balance: Uint128::one(),
+                    });
+                }
+                if !approval {
+                    return Ok(());
+                }
+                // `Approval` fails when the token has no unexpired approval for this contract,
+                // and does not report operators set through `ApproveAll`
+                let approved = deps
+                    .querier
+                    .query_wasm_smart::<cw721::ApprovalResponse>(
+                        &asset.asset_address,
+                        &Cw721QueryMsg::Approval {
+                            token_id: asset.asset_id.clone(),
+                            spender: spender.clone(),
+                            include_expired: None,
+                        },
+                    )
+                    .is_ok();
+                if !approved
+                    && !is_cw721_operator(deps, env, &asset.asset_address, owner, &spender)
+                        .map_err(query_failed)?
+                {
+                    return Err(PlaylinkAirdropErr::AssetNotApproved { index: asset_index });
+                }
+            }
+            AssetType::CW1155 => {
+                let balance: cw1155::BalanceResponse = deps
+                    .querier
+                    .query_wasm_smart(
+                        &asset.asset_address,
+                        &Cw1155QueryMsg::Balance {
+                            owner: owner.into(),
+                            token_id: asset.asset_id.clone(),
+                        },
+                    )
+                    .map_err(query_failed)?;
+                if balance.balance < amount {
+                    return Err(PlaylinkAirdropErr::InsufficientAssetBalance {
+                        index: asset_index,
+                        balance: balance.balance,
+                    });
+                }
+                if !approval {
+                    return Ok(());
+                }
+                let approved: cw1155::IsApprovedForAllResponse = deps
+                    .querier
+                    .query_wasm_smart(
+                        &asset.asset_address,
+                        &Cw1155QueryMsg::IsApprovedForAll {
+                            owner: owner.into(),
+                            operator: spender,
+                        },
+                    )
+                    .map_err(query_failed)?;
+                if !approved.approved {
+                    return Err(PlaylinkAirdropErr::AssetNotApproved { index: asset_index });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_ending_time(
+        starting_time: Uint64,
+        ending_time: Option<Uint64>,
+    ) -> Result<(), PlaylinkAirdropErr> {
+        match ending_time {
+            Some(ending_time) if ending_time <= starting_time => {
+                Err(PlaylinkAirdropErr::InvalidEndingTime {})
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn has_ended(env: &Env, campaign: &AirdropCampaign) -> bool {
+        match campaign.ending_time {
+            Some(ending_time) => env.block.time.seconds() >= ending_time.u64(),
+            None => false,
+        }
+    }
+
+    /// Escrow campaigns made only of native assets are funded as soon as they are paid for.
+    fn is_prefunded(assets: &[Asset]) -> bool {
+        assets
+            .iter()
+            .all(|asset| asset.asset_type == AssetType::Native)
+    }
+
+    fn native_totals(assets: &[Asset]) -> BTreeMap<String, Uint128> {
+        let mut totals: BTreeMap<String, Uint128> = BTreeMap::new();
+        for asset in assets.iter() {
+            if asset.asset_type == AssetType::Native {
+                *totals.entry(asset.asset_id.clone()).or_default() += asset.available_amount;
+            }
+        }
+        totals
+    }
+
+    fn coin_balances(funds: &[Coin]) -> BTreeMap<String, Uint128> {
+        let mut balances: BTreeMap<String, Uint128> = BTreeMap::new();
+        for coin in funds.iter() {
+            *balances.entry(coin.denom.clone()).or_default() += coin.amount;
+        }
+        balances
+    }
+
+    /// Takes the airdrop fee and the native assets out of `funds` and returns whatever is left
+    /// to `sender`.
+    fn settle_payment(
+        sender: &Addr,
+        mut funds: BTreeMap<String, Uint128>,
+        airdrop_fee: Uint128,
+        native_assets: &BTreeMap<String, Uint128>,
+    ) -> Result<Vec<SubMsg>, PlaylinkAirdropErr> {
+        let paid_fee = funds.entry(String::from(NATIVE_DENOM)).or_default();
+        if *paid_fee < airdrop_fee {
+            return Err(PlaylinkAirdropErr::InsufficientAirdropFee {
+                fee: airdrop_fee,
+                denom: String::from(NATIVE_DENOM),
+            });
+        }
+        *paid_fee -= airdrop_fee;
+        for (denom, amount) in native_assets.iter() {
+            let paid = funds.entry(denom.clone()).or_default();
+            if *paid < *amount {
+                return Err(PlaylinkAirdropErr::InsufficientNativeAsset {
+                    amount: *amount,
+                    denom: denom.clone(),
+                });
+            }
+            *paid -= *amount;
+        }
+
+        // Return excess
+        let excess: Vec<Coin> = funds
+            .into_iter()
+            .filter(|(_, amount)| !amount.is_zero())
+            .map(|(denom, amount)| Coin { denom, amount })
+            .collect();
+        if excess.is_empty() {
+            return Ok(vec![]);
+        }
+        Ok(vec![SubMsg::new(BankMsg::Send {
+            to_address: sender.into(),
+            amount: excess,
+        })])
+    }
+
+    /// Takes the airdrop fee from received CW20 tokens when the campaign pays in `fee_token`.
+    /// Returns the part of the fee still due in `NATIVE_DENOM`, and a refund of excess tokens.
+    fn settle_fee(
+        sender: &Addr,
+        cw20: Option<(Addr, Uint128)>,
+        fee_token: Option<&Addr>,
+        airdrop_fee: Uint128,
+    ) -> Result<(Uint128, Vec<SubMsg>), PlaylinkAirdropErr> {
+        match (cw20, fee_token) {
+            (None, None) => Ok((airdrop_fee, vec![])),
+            (None, Some(_)) if airdrop_fee.is_zero() => Ok((Uint128::zero(), vec![])),
+            (None, Some(fee_token)) => Err(PlaylinkAirdropErr::InsufficientAirdropFee {
+                fee: airdrop_fee,
+                denom: fee_token.into(),
+            }),
+            (Some((token, amount)), Some(fee_token)) if token == *fee_token => {
+                if amount < airdrop_fee {
+                    return Err(PlaylinkAirdropErr::InsufficientAirdropFee {
+                        fee: airdrop_fee,
+                        denom: fee_token.into(),
+                    });
+                }
+                let mut messages: Vec<SubMsg> = vec![];
+                if amount > airdrop_fee {
+                    messages.push(cw20_transfer_msg(&token, sender, amount - airdrop_fee)?);
+                }
+                Ok((Uint128::zero(), messages))
+            }
+            (Some((token, _)), _) => Err(PlaylinkAirdropErr::InvalidFeeToken {
+                token: token.into(),
+            }),
+        }
+    }
+
+    /// Key of `FEE_BALANCES` for a campaign paying its fee in `fee_token`.
+    pub fn fee_asset(fee_token: Option<&Addr>) -> String {
+        fee_token.map_or_else(|| String::from(NATIVE_DENOM), String::from)
+    }
+
+    fn update_fee_balance(
+        storage: &mut dyn Storage,
+        fee_asset: &str,
+        action: impl FnOnce(&mut FeeBalance) -> StdResult<()>,
+    ) -> StdResult<()> {
+        let mut balance = FEE_BALANCES
+            .may_load(storage, String::from(fee_asset))?
+            .unwrap_or_default();
+        action(&mut balance)?;
+        FEE_BALANCES.save(storage, String::from(fee_asset), &balance)
+    }
+
+    /// Records a fee paid by a campaign creator, refundable until its batches run.
+    fn collect_fee(storage: &mut dyn Storage, fee_asset: &str, fee: Uint128) -> StdResult<()> {
+        update_fee_balance(storage, fee_asset, |balance| {
+            balance.refundable += fee;
+            Ok(())
+        })
+    }
+
+    /// Records a fee returned to a campaign creator.
+    fn refund_fee(storage: &mut dyn Storage, fee_asset: &str, fee: Uint128) -> StdResult<()> {
+        update_fee_balance(storage, fee_asset, |balance| {
+            balance.refundable = balance.refundable.checked_sub(fee)?;
+            Ok(())
+        })
+    }
+
+    /// Moves a fee whose batches have run from refundable to earned.
+    fn earn_fee(storage: &mut dyn Storage, fee_asset: &str, fee: Uint128) -> StdResult<()> {
+        update_fee_balance(storage, fee_asset, |balance| {
+            balance.refundable = balance.refundable.checked_sub(fee)?;
+            balance.earned += fee;
+            Ok(())
+        })
+    }
+
+    fn cw20_transfer_msg(token: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<SubMsg> {
+        Ok(SubMsg::new(WasmMsg::Execute {
+            contract_addr: token.into(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.into(),
+                amount,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    fn lock_native(storage: &mut dyn Storage, denom: &str, amount: Uint128) -> StdResult<()> {
+        ESCROWED_NATIVE.update(storage, String::from(denom), |escrowed| -> StdResult<_> {
+            Ok(escrowed.unwrap_or_default() + amount)
+        })?;
+        Ok(())
+    }
+
+    fn release_native(storage: &mut dyn Storage, denom: &str, amount: Uint128) -> StdResult<()> {
+        ESCROWED_NATIVE.update(storage, String::from(denom), |escrowed| -> StdResult<_> {
+            Ok(escrowed.unwrap_or_default().checked_sub(amount)?)
+        })?;
+        Ok(())
+    }
+
+    /// Builds the message moving `amount` of `asset` from `owner` to `recipient`. Escrowed
+    /// assets are owned by this contract and moved without an allowance.
+    fn transfer_msg(
+        owner: &Addr,
+        escrow: bool,
+        asset: &Asset,
+        recipient: String,
+        amount: Uint128,
+    ) -> StdResult<SubMsg> {
+        let message = match asset.asset_type {
+            AssetType::Native => {
+                return Ok(SubMsg::new(BankMsg::Send {
+                    to_address: recipient,
+                    amount: coins(amount.u128(), asset.asset_id.clone()),
+                }))
+            }
+            AssetType::CW20 if escrow => {
+                to_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?
+            }
+            AssetType::CW20 => to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: owner.into(),
+                recipient,
+                amount,
+            })?,
+            AssetType::CW721 => to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient,
+                token_id: asset.asset_id.clone(),
+            })?,
+            AssetType::CW1155 => to_binary(&Cw1155ExecuteMsg::SendFrom {
+                from: owner.into(),
+                to: recipient,
+                token_id: asset.asset_id.clone(),
+                value: amount,
+                msg: None,
+            })?,
+        };
+        Ok(SubMsg::new(WasmMsg::Execute {
+            contract_addr: asset.asset_address.clone().into(),
+            msg: message,
+            funds: vec![],
+        }))
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, PlaylinkAirdropErr> {
+    execute::batch_reply(deps, msg.id, msg.result)
+}
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::EstimateAirdropFee { num_assets } => {
             to_binary(&query::estimate_airdrop_fee(deps, num_assets)?)
@@ -475,20 +2335,572 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetCampaignById { campaign_id } => {
             to_binary(&query::get_campaign_by_id(deps, campaign_id)?)
         }
+        QueryMsg::ListCampaigns {
+            start_after,
+            limit,
+            status,
+        } => to_binary(&query::list_campaigns(
+            deps,
+            env,
+            start_after,
+            limit,
+            status,
+        )?),
+        QueryMsg::CampaignsByCreator {
+            creator,
+            start_after,
+            limit,
+        } => to_binary(&query::campaigns_by_creator(
+            deps,
+            creator,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query::list_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetAccountRoles { account } => {
+            to_binary(&query::get_account_roles(deps, account)?)
+        }
+        QueryMsg::ListVoucherSigners { start_after, limit } => {
+            to_binary(&query::list_voucher_signers(deps, start_after, limit)?)
+        }
+        QueryMsg::HasReceived {
+            campaign_id,
+            recipient,
+        } => to_binary(&query::has_received(deps, campaign_id, recipient)?),
+        QueryMsg::IsVoucherNonceUsed { pubkey, nonce } => to_binary(
+            &compress_pubkey(&pubkey)
+                .is_some_and(|pubkey| USED_NONCES.has(deps.storage, (&pubkey, nonce.u64()))),
+        ),
+        QueryMsg::ListCampaignDelegates {
+            campaign_id,
+            start_after,
+            limit,
+        } => to_binary(&query::list_campaign_delegates(
+            deps,
+            campaign_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetPlatformConfig {} => to_binary(&query::get_platform_config(deps)?),
+        QueryMsg::GetVestingAllocation {
+            recipient,
+            campaign_id,
+            asset_index,
+        } => to_binary(&query::get_vesting_allocation(
+            deps,
+            env,
+            recipient,
+            campaign_id,
+            asset_index,
+        )?),
+        QueryMsg::ListVestingAllocations {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&query::list_vesting_allocations(
+            deps,
+            env,
+            recipient,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ListFailedTransfers {
+            campaign_id,
+            start_after,
+            limit,
+        } => to_binary(&query::list_failed_transfers(
+            deps,
+            campaign_id,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::GetFeeBalances {} => to_binary(&query::get_fee_balances(deps)?),
+        QueryMsg::SimulateAirdrop {
+            campaign_id,
+            asset_indexes,
+            recipients,
+            amounts,
+        } => to_binary(&query::simulate_airdrop(
+            deps,
+            env,
+            campaign_id,
+            asset_indexes,
+            recipients,
+            amounts,
+        )?),
+        QueryMsg::GetPendingAdmin {} => to_binary(&query::get_pending_admin(deps)?),
+        QueryMsg::IsClaimed {
+            campaign_id,
+            recipient,
+            asset_index,
+            amount,
+        } => to_binary(&query::is_claimed(
+            deps,
+            campaign_id,
+            recipient,
+            asset_index,
+            amount,
+        )?),
     }
 }
 
 pub mod query {
     use super::*;
+    use cw_storage_plus::Bound;
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
+    /// Campaigns scanned at most by one `ListCampaigns` page
+    const MAX_SCAN: usize = 300;
 
     pub fn get_campaign_by_id(deps: Deps, campaign_id: String) -> StdResult<AirdropCampaign> {
-        return ALL_CAMPAIGNS.load(deps.storage, campaign_id);
+        all_campaigns().load(deps.storage, campaign_id)
+    }
+
+    pub fn list_campaigns(
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+        status: Option<CampaignStatus>,
+    ) -> StdResult<CampaignsResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let mut range = all_campaigns().range(deps.storage, start, None, Order::Ascending);
+        let mut campaigns = vec![];
+        let mut last_scanned = None;
+        for (scanned, item) in range.by_ref().enumerate() {
+            let (campaign_id, campaign) = item?;
+            if status
+                .as_ref()
+                .is_none_or(|status| campaign_status(&env, &campaign) == *status)
+            {
+                campaigns.push(campaign);
+            }
+            last_scanned = Some(campaign_id);
+            if campaigns.len() == limit || scanned + 1 == MAX_SCAN {
+                break;
+            }
+        }
+        let next_start_after = range.next().and(last_scanned);
+        Ok(CampaignsResponse {
+            campaigns,
+            next_start_after,
+        })
+    }
+
+    pub fn campaigns_by_creator(
+        deps: Deps,
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<CampaignsResponse> {
+        let creator = deps.api.addr_validate(&creator)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        let mut campaigns = all_campaigns()
+            .idx
+            .creator
+            .prefix(creator)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit + 1)
+            .map(|item| item.map(|(_, campaign)| campaign))
+            .collect::<StdResult<Vec<_>>>()?;
+        let next_start_after = if campaigns.len() > limit {
+            campaigns.pop();
+            campaigns
+                .last()
+                .map(|campaign| campaign.campaign_id.clone())
+        } else {
+            None
+        };
+        Ok(CampaignsResponse {
+            campaigns,
+            next_start_after,
+        })
+    }
+
+    pub fn campaign_status(env: &Env, campaign: &AirdropCampaign) -> CampaignStatus {
+        if execute::has_ended(env, campaign) {
+            CampaignStatus::Ended
+        } else if env.block.time.seconds() < campaign.starting_time.u64() {
+            CampaignStatus::Upcoming
+        } else {
+            CampaignStatus::Active
+        }
+    }
+
+    pub fn list_role_members(
+        deps: Deps,
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<RoleMembersResponse> {
+        let start_after = start_after
+            .map(|address| deps.api.addr_validate(&address))
+            .transpose()?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let members = ROLES
+            .prefix(role.key())
+            .keys(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(RoleMembersResponse { members })
+    }
+
+    pub fn has_received(deps: Deps, campaign_id: String, recipient: String) -> StdResult<bool> {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        Ok(match all_campaigns().may_load(deps.storage, campaign_id)? {
+            Some(campaign) => {
+                CAMPAIGN_RECIPIENTS.has(deps.storage, (campaign.instance.u64(), recipient))
+            }
+            None => false,
+        })
+    }
+
+    pub fn list_voucher_signers(
+        deps: Deps,
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    ) -> StdResult<VoucherSignersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let pubkeys = VOUCHER_SIGNERS
+            .keys_raw(
+                deps.storage,
+                start_after.as_deref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(Binary::from)
+            .collect();
+        Ok(VoucherSignersResponse { pubkeys })
+    }
+
+    pub fn get_account_roles(deps: Deps, account: String) -> StdResult<AccountRolesResponse> {
+        let account = deps.api.addr_validate(&account)?;
+        let roles = Role::ALL
+            .into_iter()
+            .filter(|role| ROLES.has(deps.storage, (role.key(), account.clone())))
+            .collect();
+        Ok(AccountRolesResponse { roles })
+    }
+
+    pub fn list_campaign_delegates(
+        deps: Deps,
+        campaign_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<DelegatesResponse> {
+        let start_after = start_after
+            .map(|address| deps.api.addr_validate(&address))
+            .transpose()?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let campaign = match all_campaigns().may_load(deps.storage, campaign_id)? {
+            Some(campaign) => campaign,
+            None => return Ok(DelegatesResponse { delegates: vec![] }),
+        };
+        let delegates = CAMPAIGN_DELEGATES
+            .prefix(campaign.instance.u64())
+            .keys(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(DelegatesResponse { delegates })
+    }
+
+    pub fn get_platform_config(deps: Deps) -> StdResult<PlatformConfigResponse> {
+        Ok(PlatformConfigResponse {
+            platform: AIRDROP_PLATFORM.load(deps.storage)?,
+            paused: PAUSED.may_load(deps.storage)?.unwrap_or(false),
+            contract_version: get_contract_version(deps.storage)?,
+        })
+    }
+
+    pub fn get_vesting_allocation(
+        deps: Deps,
+        env: Env,
+        recipient: String,
+        campaign_id: String,
+        asset_index: Uint64,
+    ) -> StdResult<VestingStatus> {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        let allocation = VESTING_ALLOCATIONS.load(
+            deps.storage,
+            (recipient, campaign_id.clone(), asset_index.u64()),
+        )?;
+        Ok(vesting_status(&env, campaign_id, asset_index, allocation))
+    }
+
+    pub fn list_vesting_allocations(
+        deps: Deps,
+        env: Env,
+        recipient: String,
+        start_after: Option<(String, Uint64)>,
+        limit: Option<u32>,
+    ) -> StdResult<VestingAllocationsResponse> {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after
+            .map(|(campaign_id, asset_index)| Bound::exclusive((campaign_id, asset_index.u64())));
+        let allocations = VESTING_ALLOCATIONS
+            .sub_prefix(recipient)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|((campaign_id, asset_index), allocation)| {
+                    vesting_status(&env, campaign_id, Uint64::from(asset_index), allocation)
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(VestingAllocationsResponse { allocations })
+    }
+
+    fn vesting_status(
+        env: &Env,
+        campaign_id: String,
+        asset_index: Uint64,
+        allocation: VestingAllocation,
+    ) -> VestingStatus {
+        let vested_amount = allocation
+            .schedule
+            .vested_amount(allocation.total_amount, env.block.time.seconds());
+        VestingStatus {
+            campaign_id,
+            asset_index,
+            withdrawable_amount: vested_amount - allocation.withdrawn_amount,
+            vested_amount,
+            allocation,
+        }
+    }
+
+    pub fn list_failed_transfers(
+        deps: Deps,
+        campaign_id: String,
+        start_after: Option<(String, Uint64)>,
+        limit: Option<u32>,
+    ) -> StdResult<FailedTransfersResponse> {
+        let start_after = match start_after {
+            Some((recipient, asset_index)) => {
+                Some((deps.api.addr_validate(&recipient)?, asset_index.u64()))
+            }
+            None => None,
+        };
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let campaign = match all_campaigns().may_load(deps.storage, campaign_id)? {
+            Some(campaign) => campaign,
+            None => {
+                return Ok(FailedTransfersResponse {
+                    failed_transfers: vec![],
+                })
+            }
+        };
+        let failed_transfers = FAILED_TRANSFERS
+            .sub_prefix(campaign.instance.u64())
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .map(|item| item.map(|(_, failed_transfer)| failed_transfer))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(FailedTransfersResponse { failed_transfers })
+    }
+
+    pub fn get_fee_balances(deps: Deps) -> StdResult<FeeBalancesResponse> {
+        let fee_balances = FEE_BALANCES
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                item.map(|(fee_asset, balance)| FeeAssetBalance {
+                    fee_asset,
+                    earned: balance.earned,
+                    refundable: balance.refundable,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let escrowed_native = ESCROWED_NATIVE
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((_, amount)) if amount.is_zero()))
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(FeeBalancesResponse {
+            fee_balances,
+            escrowed_native,
+        })
+    }
+
+    pub fn simulate_airdrop(
+        deps: Deps,
+        env: Env,
+        campaign_id: String,
+        asset_indexes: Vec<Uint64>,
+        recipients: Vec<String>,
+        amounts: Option<Vec<Uint128>>,
+    ) -> StdResult<SimulateAirdropResponse> {
+        let mut campaign = match execute::check_airdrop_batch(
+            deps,
+            &env,
+            campaign_id,
+            &asset_indexes,
+            &recipients,
+            amounts.as_ref(),
+        ) {
+            Ok(campaign) => campaign,
+            Err(err) => {
+                return Ok(SimulateAirdropResponse {
+                    error: Some(err.to_string()),
+                    entries: vec![],
+                })
+            }
+        };
+
+        // Entries drawing the same token add up against the balance and allowance of its owner
+        let owner = execute::asset_owner(&env, &campaign);
+        let mut drawn: BTreeMap<(Addr, String), Uint128> = BTreeMap::new();
+        let mut received: BTreeSet<Addr> = BTreeSet::new();
+        let mut entries: Vec<SimulatedTransfer> = vec![];
+        for (i, asset_index) in asset_indexes.iter().enumerate() {
+            let amount = amounts.as_ref().map(|amounts| amounts[i]);
+            let (amount, error) = match execute::check_airdrop_entry(
+                deps.api,
+                &campaign,
+                *asset_index,
+                &recipients[i],
+                amount,
+            ) {
+                Ok((recipient, amount))
+                    if campaign.unique_recipients
+                        && (received.contains(&recipient)
+                            || CAMPAIGN_RECIPIENTS.has(
+                                deps.storage,
+                                (campaign.instance.u64(), recipient.clone()),
+                            )) =>
+                {
+                    let err = PlaylinkAirdropErr::RecipientAlreadyReceived {
+                        recipient: recipient.into(),
+                    };
+                    (amount, Some(err.to_string()))
+                }
+                Ok((recipient, amount)) => {
+                    received.insert(recipient);
+                    let asset = &mut campaign.assets[asset_index.u64() as usize];
+                    asset.available_amount -= amount;
+                    let token = (asset.asset_address.clone(), asset.asset_id.clone());
+                    let total = drawn.get(&token).copied().unwrap_or_default() + amount;
+                    match execute::check_asset_transfer(
+                        deps,
+                        &env,
+                        asset,
+                        *asset_index,
+                        &owner,
+                        !campaign.escrow,
+                        total,
+                    ) {
+                        Ok(()) => {
+                            drawn.insert(token, total);
+                            (amount, None)
+                        }
+                        Err(err) => (amount, Some(err.to_string())),
+                    }
+                }
+                Err(err) => (amount.unwrap_or_default(), Some(err.to_string())),
+            };
+            entries.push(SimulatedTransfer {
+                asset_index: *asset_index,
+                recipient: recipients[i].clone(),
+                amount,
+                error,
+            });
+        }
+        Ok(SimulateAirdropResponse {
+            error: None,
+            entries,
+        })
+    }
+
+    pub fn get_pending_admin(deps: Deps) -> StdResult<Option<PendingAdmin>> {
+        PENDING_ADMIN.may_load(deps.storage)
+    }
+
+    pub fn is_claimed(
+        deps: Deps,
+        campaign_id: String,
+        recipient: String,
+        asset_index: Uint64,
+        amount: Uint128,
+    ) -> StdResult<bool> {
+        let recipient = deps.api.addr_validate(&recipient)?;
+        let leaf = merkle_leaf(recipient.as_str(), asset_index, amount);
+        Ok(match all_campaigns().may_load(deps.storage, campaign_id)? {
+            Some(campaign) => {
+                execute::is_leaf_claimed(deps.storage, &campaign, &recipient, asset_index, &leaf)
+            }
+            None => false,
+        })
     }
 
     pub fn estimate_airdrop_fee(deps: Deps, num_assets: Uint64) -> StdResult<Uint128> {
         let platform = AIRDROP_PLATFORM.load(deps.storage)?;
         let num_required_batches =
-            (num_assets + platform.max_match_size - Uint64::one()) / platform.max_match_size;
+            (num_assets + platform.max_batch_size - Uint64::one()) / platform.max_batch_size;
         Ok(Uint128::from(num_required_batches) * platform.fee_per_batch)
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, PlaylinkAirdropErr> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(PlaylinkAirdropErr::InvalidContractName {
+            name: stored.contract,
+        });
+    }
+    let from_version = parse_version(&stored.version)?;
+    let to_version = parse_version(CONTRACT_VERSION)?;
+    if from_version > to_version {
+        return Err(PlaylinkAirdropErr::CannotDowngrade {
+            from: stored.version,
+            to: String::from(CONTRACT_VERSION),
+        });
+    }
+
+    // Schema migrations, oldest first
+    if from_version < Version::new(0, 2, 0) {
+        migrate_v0_2(deps.storage)?;
+    }
+    if from_version < Version::new(0, 3, 0) {
+        migrate_v0_3(deps.storage)?;
+    }
+    if from_version < Version::new(0, 4, 0) {
+        let native_balance = deps
+            .querier
+            .query_balance(env.contract.address, NATIVE_DENOM)?
+            .amount;
+        migrate_v0_4(deps.storage, native_balance)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    Version::parse(version).map_err(|err| StdError::generic_err(err.to_string()))
+}
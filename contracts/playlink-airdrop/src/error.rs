@@ -1,9 +1,9 @@
 use cosmwasm_std::{StdError, Uint128, Uint64};
 use thiserror::Error;
 
-use crate::helpers::AssetType;
+use crate::helpers::{AssetType, Role};
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, PartialEq)]
 pub enum PlaylinkAirdropErr {
     #[error("{0}")]
     Std(#[from] StdError),
@@ -11,11 +11,32 @@ pub enum PlaylinkAirdropErr {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("PlaylinkAirdrop: cannot migrate from contract {name:?}")]
+    InvalidContractName { name: String },
+
+    #[error("PlaylinkAirdrop: cannot migrate from version {from:?} to {to:?}")]
+    CannotDowngrade { from: String, to: String },
+
     #[error("PlaylinkAirdrop: {account:?} is not admin")]
     NotAdmin { account: String },
 
-    #[error("PlaylinkAirdrop: caller is not operator ({account:?})")]
-    NotOperator { account: String },
+    #[error("PlaylinkAirdrop: {account:?} is not pending admin")]
+    NotPendingAdmin { account: String },
+
+    #[error("PlaylinkAirdrop: admin proposal expired")]
+    AdminProposalExpired {},
+
+    #[error("PlaylinkAirdrop: expiry too low")]
+    LowExpiry {},
+
+    #[error("PlaylinkAirdrop: {account:?} does not have role {role:?}")]
+    MissingRole { account: String, role: Role },
+
+    #[error("PlaylinkAirdrop: platform is paused")]
+    PlatformPaused {},
+
+    #[error("PlaylinkAirdrop: campaign is paused ({campaign_id:?})")]
+    CampaignPaused { campaign_id: String },
 
     #[error("PlaylinkAirdrop: lengths mismatch")]
     LengthMismatch {},
@@ -26,6 +47,9 @@ pub enum PlaylinkAirdropErr {
     #[error("PlaylinkAirdrop: insuffient airdrop fee (required {fee:?} {denom:?})")]
     InsufficientAirdropFee { fee: Uint128, denom: String },
 
+    #[error("PlaylinkAirdrop: {token:?} is not accepted as airdrop fee")]
+    InvalidFeeToken { token: String },
+
     #[error("PlaylinkAirdrop: starting time too low")]
     LowStartingTime {},
 
@@ -35,6 +59,21 @@ pub enum PlaylinkAirdropErr {
     #[error("PlaylinkAirdrop: invalid CW20 asset ID ({asset_id:?})")]
     InvalidAssetId { asset_id: String },
 
+    #[error("PlaylinkAirdrop: invalid native asset address ({asset_address:?})")]
+    InvalidAssetAddress { asset_address: String },
+
+    #[error("PlaylinkAirdrop: insufficient native asset funds (required {amount:?} {denom:?})")]
+    InsufficientNativeAsset { amount: Uint128, denom: String },
+
+    #[error("PlaylinkAirdrop: vesting is only supported for fungible assets ({asset_type:?})")]
+    VestingNotSupported { asset_type: AssetType },
+
+    #[error("PlaylinkAirdrop: invalid vesting schedule")]
+    InvalidVestingSchedule {},
+
+    #[error("PlaylinkAirdrop: nothing to withdraw")]
+    NothingToWithdraw {},
+
     #[error("PlaylinkAirdrop: invalid CW721 amount ({asset_amount:?})")]
     InvalidAssetAmount { asset_amount: Uint128 },
 
@@ -44,6 +83,15 @@ pub enum PlaylinkAirdropErr {
     #[error("PlaylinkAirdrop: not campaign creator ({campaign_creator:?})")]
     NotCampaignCreator { campaign_creator: String },
 
+    #[error("PlaylinkAirdrop: ending time must be after starting time")]
+    InvalidEndingTime {},
+
+    #[error("PlaylinkAirdrop: campaign ended ({campaign_id:?})")]
+    CampaignEnded { campaign_id: String },
+
+    #[error("PlaylinkAirdrop: campaign not ended yet ({campaign_id:?})")]
+    CampaignNotEnded { campaign_id: String },
+
     #[error("PlaylinkAirdrop: campaign started, cannot update campaign")]
     UpdateNotAllowed { starting_time: Uint64 },
 
@@ -58,4 +106,70 @@ pub enum PlaylinkAirdropErr {
 
     #[error("PlaylinkAirdrop: batch size ({size:?}) must be greater than zero")]
     InvalidMaxBatchSize { size: Uint64 },
+
+    #[error("PlaylinkAirdrop: invalid merkle root ({merkle_root:?})")]
+    InvalidMerkleRoot { merkle_root: String },
+
+    #[error("PlaylinkAirdrop: invalid merkle proof")]
+    InvalidMerkleProof {},
+
+    #[error("PlaylinkAirdrop: campaign is not claimable ({campaign_id:?})")]
+    NotMerkleCampaign { campaign_id: String },
+
+    #[error("PlaylinkAirdrop: campaign can only be claimed ({campaign_id:?})")]
+    ClaimOnlyCampaign { campaign_id: String },
+
+    #[error("PlaylinkAirdrop: asset {index:?} already claimed")]
+    AlreadyClaimed { index: Uint64 },
+
+    #[error("PlaylinkAirdrop: {recipient:?} already received from this campaign")]
+    RecipientAlreadyReceived { recipient: String },
+
+    #[error("PlaylinkAirdrop: invalid secp256k1 public key")]
+    InvalidVoucherSigner {},
+
+    #[error("PlaylinkAirdrop: voucher signer is not registered")]
+    VoucherSignerNotRegistered {},
+
+    #[error("PlaylinkAirdrop: invalid voucher signature")]
+    InvalidVoucherSignature {},
+
+    #[error("PlaylinkAirdrop: voucher expired ({nonce:?})")]
+    VoucherExpired { nonce: Uint64 },
+
+    #[error("PlaylinkAirdrop: voucher already redeemed ({nonce:?})")]
+    VoucherAlreadyUsed { nonce: Uint64 },
+
+    #[error("PlaylinkAirdrop: amount exceeds available amount of asset {index:?} ({available:?})")]
+    AssetAmountExceeded { index: Uint64, available: Uint128 },
+
+    #[error("PlaylinkAirdrop: asset {index:?} is not held by {owner:?}")]
+    AssetNotOwned { index: Uint64, owner: String },
+
+    #[error("PlaylinkAirdrop: balance of asset {index:?} too low ({balance:?})")]
+    InsufficientAssetBalance { index: Uint64, balance: Uint128 },
+
+    #[error("PlaylinkAirdrop: allowance for asset {index:?} too low ({allowance:?})")]
+    InsufficientAllowance { index: Uint64, allowance: Uint128 },
+
+    #[error("PlaylinkAirdrop: asset {index:?} is not approved for transfer")]
+    AssetNotApproved { index: Uint64 },
+
+    #[error("PlaylinkAirdrop: cannot query contract of asset {index:?} ({error})")]
+    AssetQueryFailed { index: Uint64, error: String },
+
+    #[error("PlaylinkAirdrop: campaign is not in escrow mode ({campaign_id:?})")]
+    NotEscrowCampaign { campaign_id: String },
+
+    #[error("PlaylinkAirdrop: campaign is not funded yet ({campaign_id:?})")]
+    CampaignNotFunded { campaign_id: String },
+
+    #[error("PlaylinkAirdrop: campaign is already funded ({campaign_id:?})")]
+    CampaignAlreadyFunded { campaign_id: String },
+
+    #[error("PlaylinkAirdrop: campaign funding started, cannot update campaign")]
+    CampaignFundingStarted { campaign_id: String },
+
+    #[error("PlaylinkAirdrop: deposit exceeds declared assets ({excess:?})")]
+    ExcessEscrowDeposit { excess: Uint128 },
 }
@@ -0,0 +1,111 @@
+//! Events emitted by the contract. The chain reports them as `wasm-playlink_airdrop`, and
+//! indexers read them instead of decoding submessages. Their attributes are a stable API:
+//! attributes may be added, but existing ones are never renamed or removed. Optional values
+//! are left out when unset, since attribute values cannot be empty.
+
+use cosmwasm_std::{Addr, Event, Uint128, Uint64};
+
+use crate::{helpers::AirdropCampaign, state::AirdropPlatform};
+
+pub const EVENT_TYPE: &str = "playlink_airdrop";
+
+/// Base event of every action, carrying only `action`
+pub fn event(action: &str) -> Event {
+    Event::new(EVENT_TYPE).add_attribute("action", action)
+}
+
+fn add_optional_attribute(event: Event, key: &str, value: Option<String>) -> Event {
+    match value {
+        Some(value) if !value.is_empty() => event.add_attribute(key, value),
+        _ => event,
+    }
+}
+
+/// One asset paid out of a campaign, or put into a vesting allocation of the recipient
+pub fn transfer_event(
+    action: &str,
+    campaign: &AirdropCampaign,
+    asset_index: Uint64,
+    recipient: &Addr,
+    amount: Uint128,
+) -> Event {
+    let asset = &campaign.assets[asset_index.u64() as usize];
+    let event = event(action)
+        .add_attribute("campaign_id", &campaign.campaign_id)
+        .add_attribute("asset_index", asset_index)
+        .add_attribute("asset_type", asset.asset_type.as_str());
+    // Native assets have no address and CW20 assets no id
+    let event = add_optional_attribute(
+        event,
+        "asset_address",
+        Some(asset.asset_address.to_string()),
+    );
+    let event = add_optional_attribute(event, "asset_id", Some(asset.asset_id.clone()));
+    event
+        .add_attribute("amount", amount)
+        .add_attribute("recipient", recipient)
+        .add_attribute("vesting", asset.vesting.is_some().to_string())
+}
+
+/// Settings of a campaign after it was created or updated
+pub fn campaign_event(action: &str, campaign: &AirdropCampaign, fee_denom: &str) -> Event {
+    let event = event(action)
+        .add_attribute("campaign_id", &campaign.campaign_id)
+        .add_attribute("creator", &campaign.creator)
+        .add_attribute("num_assets", campaign.assets.len().to_string())
+        .add_attribute("total_available_assets", campaign.total_available_assets)
+        .add_attribute("max_batch_size", campaign.max_batch_size)
+        .add_attribute("starting_time", campaign.starting_time);
+    let event = add_optional_attribute(
+        event,
+        "ending_time",
+        campaign.ending_time.map(|time| time.to_string()),
+    );
+    let event = event
+        .add_attribute("airdrop_fee", campaign.airdrop_fee)
+        .add_attribute("fee_denom", fee_denom);
+    add_optional_attribute(event, "merkle_root", campaign.merkle_root.clone())
+        .add_attribute("escrow", campaign.escrow.to_string())
+        .add_attribute("self_serve", campaign.self_serve.to_string())
+        .add_attribute("unique_recipients", campaign.unique_recipients.to_string())
+}
+
+/// A campaign removed by its creator, with the part of the fee sent back
+pub fn campaign_release_event(
+    action: &str,
+    campaign: &AirdropCampaign,
+    refunded_fee: Uint128,
+    fee_denom: &str,
+) -> Event {
+    event(action)
+        .add_attribute("campaign_id", &campaign.campaign_id)
+        .add_attribute("creator", &campaign.creator)
+        .add_attribute("executed_batches", campaign.executed_batches)
+        .add_attribute("airdrop_fee", campaign.airdrop_fee)
+        .add_attribute("refunded_fee", refunded_fee)
+        .add_attribute("fee_denom", fee_denom)
+}
+
+/// Whole platform configuration after any of its settings changed
+pub fn config_event(action: &str, platform: &AirdropPlatform) -> Event {
+    let event = add_optional_attribute(
+        event(action),
+        "admin",
+        platform.admin.as_ref().map(Addr::to_string),
+    )
+    .add_attribute("max_batch_size", platform.max_batch_size)
+    .add_attribute("fee_per_batch", platform.fee_per_batch);
+    add_optional_attribute(
+        event,
+        "fee_token",
+        platform.fee_token.as_ref().map(Addr::to_string),
+    )
+}
+
+/// Earned fees of one fee asset sent out by `WithdrawAirdropFee`
+pub fn fee_withdrawal_event(recipient: &Addr, fee_denom: &str, amount: Uint128) -> Event {
+    event("withdraw_airdrop_fee")
+        .add_attribute("recipient", recipient)
+        .add_attribute("fee_denom", fee_denom)
+        .add_attribute("amount", amount)
+}
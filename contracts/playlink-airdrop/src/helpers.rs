@@ -1,7 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use cosmwasm_std::{Addr, Uint128, Uint64};
+use cosmwasm_std::{to_vec, Addr, StdResult, Uint128, Uint64};
 
 pub const NATIVE_DENOM: &str = "flavor";
 
@@ -10,6 +11,20 @@ pub enum AssetType {
     CW20,
     CW721,
     CW1155,
+    /// Bank coin held by this contract; `asset_id` is the denom and `asset_address` is empty
+    Native,
+}
+
+impl AssetType {
+    /// Name of the asset type in events, spelled as in messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AssetType::CW20 => "CW20",
+            AssetType::CW721 => "CW721",
+            AssetType::CW1155 => "CW1155",
+            AssetType::Native => "Native",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -18,6 +33,41 @@ pub struct Asset {
     pub asset_address: Addr,
     pub asset_id: String,
     pub available_amount: Uint128,
+    /// Fungible assets only. Amounts sent to recipients are locked in this contract and unlock
+    /// linearly, to be pulled through `WithdrawVested`.
+    #[serde(default)]
+    pub vesting: Option<VestingSchedule>,
+}
+
+/// Linear unlock from `start_time` to `end_time`, with nothing withdrawable before
+/// `cliff_time`. Times are block times in seconds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VestingSchedule {
+    pub start_time: Uint64,
+    pub cliff_time: Uint64,
+    pub end_time: Uint64,
+}
+
+impl VestingSchedule {
+    pub fn is_valid(&self) -> bool {
+        self.start_time <= self.cliff_time
+            && self.cliff_time <= self.end_time
+            && self.start_time < self.end_time
+    }
+
+    /// Part of `total` unlocked at block time `now`.
+    pub fn vested_amount(&self, total: Uint128, now: u64) -> Uint128 {
+        if now < self.cliff_time.u64() {
+            Uint128::zero()
+        } else if now >= self.end_time.u64() {
+            total
+        } else {
+            total.multiply_ratio(
+                now - self.start_time.u64(),
+                self.end_time.u64() - self.start_time.u64(),
+            )
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -29,4 +79,162 @@ pub struct AirdropCampaign {
     pub starting_time: Uint64,
     pub total_available_assets: Uint128,
     pub airdrop_fee: Uint128,
+    /// Hex-encoded Merkle root over `(recipient, asset_index, amount)` leaves. Campaigns with a
+    /// root are paid out through `Claim` instead of operator airdrops.
+    #[serde(default)]
+    pub merkle_root: Option<String>,
+    /// Escrow campaigns hold their assets in this contract and pay out from its own balance.
+    #[serde(default)]
+    pub escrow: bool,
+    /// Set once every declared asset of an escrow campaign has been deposited.
+    #[serde(default)]
+    pub funded: bool,
+    /// Number of `Airdrop` batches run so far, used to pro-rate fee refunds.
+    #[serde(default)]
+    pub executed_batches: Uint64,
+    /// `total_available_assets` when the campaign was created or last updated, used to pro-rate
+    /// fee refunds by the amount paid out.
+    #[serde(default)]
+    pub initial_assets: Uint128,
+    /// Part of `airdrop_fee` already earned by the platform.
+    #[serde(default)]
+    pub earned_fee: Uint128,
+    /// Once reached, assets can no longer be paid out and the creator can close the campaign.
+    #[serde(default)]
+    pub ending_time: Option<Uint64>,
+    /// CW20 token the airdrop fee was paid in, `None` for `NATIVE_DENOM`.
+    #[serde(default)]
+    pub fee_token: Option<Addr>,
+    /// Paused campaigns cannot pay out until unpaused by the creator or the admin.
+    #[serde(default)]
+    pub paused: bool,
+    /// Lets the creator run `Airdrop` batches besides operators and delegates.
+    #[serde(default)]
+    pub self_serve: bool,
+    /// Each wallet can receive from the campaign once; recipients are kept in
+    /// `CAMPAIGN_RECIPIENTS`.
+    #[serde(default)]
+    pub unique_recipients: bool,
+    /// Sequence number of the campaign, never reused by a campaign taking over its ID. Records
+    /// kept per campaign are keyed by it, so they need no clearing when the campaign is removed.
+    /// Campaigns created before 0.4.0 have instance 0.
+    #[serde(default)]
+    pub instance: Uint64,
+}
+
+/// Platform permissions granted by the admin, who holds every role implicitly
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Sets `fee_per_batch` and the fee token
+    FeeManager,
+    /// Sets `max_batch_size`
+    BatchSizeManager,
+    /// Runs `Airdrop` on every campaign
+    AirdropExecutor,
+    /// Pauses the platform and any campaign
+    Pauser,
+    /// Withdraws earned airdrop fees
+    Treasurer,
+}
+
+impl Role {
+    pub const ALL: [Role; 5] = [
+        Role::FeeManager,
+        Role::BatchSizeManager,
+        Role::AirdropExecutor,
+        Role::Pauser,
+        Role::Treasurer,
+    ];
+
+    /// Storage key of the role in `ROLES`
+    pub fn key(&self) -> &'static str {
+        match self {
+            Role::FeeManager => "fee_manager",
+            Role::BatchSizeManager => "batch_size_manager",
+            Role::AirdropExecutor => "airdrop_executor",
+            Role::Pauser => "pauser",
+            Role::Treasurer => "treasurer",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CampaignStatus {
+    /// Starting time not reached yet
+    Upcoming,
+    /// Started and not ended yet. Exhausted campaigns are removed, so they are never listed.
+    Active,
+    /// Ending time reached with assets left
+    Ended,
+}
+
+/// Reward decided off-chain and signed by a registered voucher signer. Anyone can submit it
+/// through `ClaimWithVoucher`; the assets always go to `recipient`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Voucher {
+    pub campaign_id: String,
+    pub recipient: String,
+    pub asset_index: Uint64,
+    pub amount: Uint128,
+    /// Unique per signer; each nonce can be redeemed once
+    pub nonce: Uint64,
+    /// Block time in seconds from which the voucher is no longer accepted
+    pub expiry: Uint64,
+}
+
+/// Hashes the message a voucher signer signs as `sha256(contract_address || voucher_json)`, so
+/// a voucher cannot be replayed on another instance of this contract.
+pub fn voucher_digest(contract_address: &Addr, voucher: &Voucher) -> StdResult<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    hasher.update(contract_address.as_bytes());
+    hasher.update(to_vec(voucher)?);
+    Ok(hasher.finalize().into())
+}
+
+/// Compressed form of a secp256k1 public key, so both encodings of a key name the same voucher
+/// signer. `None` unless `pubkey` is a 33-byte compressed or 65-byte uncompressed key.
+pub fn compress_pubkey(pubkey: &[u8]) -> Option<Vec<u8>> {
+    match (pubkey.len(), pubkey.first()) {
+        (33, Some(2 | 3)) => Some(pubkey.to_vec()),
+        (65, Some(4)) => {
+            // The prefix of a compressed key carries the parity of y
+            let mut compressed = vec![2 | (pubkey[64] & 1)];
+            compressed.extend_from_slice(&pubkey[1..33]);
+            Some(compressed)
+        }
+        _ => None,
+    }
+}
+
+/// Hashes a claim leaf as `sha256("{recipient}:{asset_index}:{amount}")`.
+pub fn merkle_leaf(recipient: &str, asset_index: Uint64, amount: Uint128) -> [u8; 32] {
+    Sha256::digest(format!("{}:{}:{}", recipient, asset_index, amount).as_bytes()).into()
+}
+
+/// Checks a hex-encoded proof against a hex-encoded root. Sibling pairs are sorted before
+/// hashing, so proofs do not need to carry left/right positions.
+pub fn verify_merkle_proof(leaf: [u8; 32], proof: &[String], root: &str) -> bool {
+    let mut hash = leaf;
+    for sibling in proof.iter() {
+        let sibling: [u8; 32] = match hex::decode(sibling).ok().and_then(|b| b.try_into().ok()) {
+            Some(sibling) => sibling,
+            None => return false,
+        };
+        let (first, second) = if hash <= sibling {
+            (hash, sibling)
+        } else {
+            (sibling, hash)
+        };
+        let mut hasher = Sha256::new();
+        hasher.update(first);
+        hasher.update(second);
+        hash = hasher.finalize().into();
+    }
+    hex::encode(hash) == root.to_lowercase()
+}
+
+pub fn is_valid_merkle_root(root: &str) -> bool {
+    matches!(hex::decode(root), Ok(bytes) if bytes.len() == 32)
 }
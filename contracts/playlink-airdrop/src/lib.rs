@@ -1,6 +1,8 @@
 pub mod contract;
 mod error;
+pub mod events;
 pub mod helpers;
+pub mod migrations;
 pub mod msg;
 pub mod state;
 pub mod test;
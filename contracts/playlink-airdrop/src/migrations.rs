@@ -0,0 +1,105 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128, Uint64};
+use cw_storage_plus::{Item, Map};
+
+use crate::{
+    helpers::{AirdropCampaign, Role, NATIVE_DENOM},
+    state::{
+        all_campaigns, AirdropPlatform, FeeBalance, AIRDROP_PLATFORM, ESCROWED_NATIVE,
+        FEE_BALANCES, ROLES,
+    },
+};
+
+/// Storage layouts written by earlier releases, loaded only while migrating.
+pub mod legacy {
+    use super::*;
+
+    /// `AirdropPlatform` as stored up to 0.1.x
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    pub struct AirdropPlatformV0_1 {
+        pub admin: Addr,
+        pub max_match_size: Uint64,
+        pub fee_per_batch: Uint128,
+    }
+
+    pub const AIRDROP_PLATFORM_V0_1: Item<AirdropPlatformV0_1> = Item::new("airdrop_platform");
+
+    /// Campaigns as stored up to 0.2.x, without secondary indexes
+    pub const ALL_CAMPAIGNS_V0_2: Map<String, AirdropCampaign> = Map::new("all_campaigns");
+
+    /// Operators as stored up to 0.3.x, which could airdrop and change the fee and batch size
+    pub const OPERATORS_V0_3: Map<Addr, bool> = Map::new("operators");
+}
+
+/// 0.1.x -> 0.2.0: `AirdropPlatform.max_match_size` is renamed to `max_batch_size`.
+pub fn migrate_v0_2(storage: &mut dyn Storage) -> StdResult<()> {
+    let platform = legacy::AIRDROP_PLATFORM_V0_1.load(storage)?;
+    AIRDROP_PLATFORM.save(
+        storage,
+        &AirdropPlatform {
+            admin: Some(platform.admin),
+            max_batch_size: platform.max_match_size,
+            fee_per_batch: platform.fee_per_batch,
+            fee_token: None,
+        },
+    )
+}
+
+/// 0.2.x -> 0.3.0: campaigns gain a `creator` index, built here for the existing entries.
+pub fn migrate_v0_3(storage: &mut dyn Storage) -> StdResult<()> {
+    let campaigns = legacy::ALL_CAMPAIGNS_V0_2
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let campaigns_map = all_campaigns();
+    for (campaign_id, campaign) in campaigns {
+        campaigns_map.replace(storage, campaign_id, Some(&campaign), None)?;
+    }
+    Ok(())
+}
+
+/// 0.3.x -> 0.4.0: operators are replaced by roles. Current operators become airdrop
+/// executors, and fee and batch size managers have to be granted by the admin.
+///
+/// Airdrop fees are now tracked in `FEE_BALANCES`. Earlier releases let the admin withdraw
+/// every fee at any time, so fees of existing campaigns count as earned and are not refunded.
+/// The part of `native_balance` not held for campaign assets is seeded as earned.
+///
+/// Merkle claims are now keyed by leaf. Claims recorded so far stay in `LEGACY_CLAIMS` and only
+/// count for the existing campaigns, which keep instance 0.
+pub fn migrate_v0_4(storage: &mut dyn Storage, native_balance: Uint128) -> StdResult<()> {
+    let campaigns = all_campaigns()
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (campaign_id, mut campaign) in campaigns {
+        campaign.earned_fee = campaign.airdrop_fee;
+        all_campaigns().save(storage, campaign_id, &campaign)?;
+    }
+    let escrowed = ESCROWED_NATIVE
+        .may_load(storage, String::from(NATIVE_DENOM))?
+        .unwrap_or_default();
+    FEE_BALANCES.save(
+        storage,
+        String::from(NATIVE_DENOM),
+        &FeeBalance {
+            earned: native_balance.saturating_sub(escrowed),
+            refundable: Uint128::zero(),
+        },
+    )?;
+
+    let operators = legacy::OPERATORS_V0_3
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (operator, is_operator) in operators {
+        if is_operator {
+            ROLES.save(
+                storage,
+                (Role::AirdropExecutor.key(), operator.clone()),
+                &true,
+            )?;
+        }
+        legacy::OPERATORS_V0_3.remove(storage, operator);
+    }
+    Ok(())
+}
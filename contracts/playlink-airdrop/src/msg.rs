@@ -1,6 +1,15 @@
-use crate::helpers::{AirdropCampaign, Asset};
+use crate::{
+    helpers::{AirdropCampaign, Asset, CampaignStatus, Role, Voucher},
+    state::{AirdropPlatform, FailedTransfer, PendingAdmin, VestingAllocation},
+};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Uint128, Uint64};
+use cosmwasm_std::{Addr, Binary, Coin, Uint128, Uint64};
+use cw1155::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
+use cw2::ContractVersion;
+use cw20::Cw20ReceiveMsg;
+use cw721::Cw721ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -8,11 +17,36 @@ pub struct InstantiateMsg {
     pub fee_per_batch: Uint128,
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Settings given when a campaign is created or updated
+#[cw_serde]
+pub struct CampaignSettings {
+    pub campaign_id: String,
+    pub assets: Vec<Asset>,
+    pub starting_time: Uint64,
+    pub merkle_root: Option<String>,
+    pub escrow: Option<bool>,
+    /// Block time in seconds after which the campaign can no longer pay out
+    pub ending_time: Option<Uint64>,
+    /// Queries the token contracts and rejects assets the creator cannot cover
+    pub strict: Option<bool>,
+    /// Lets the creator run `Airdrop` batches on this campaign
+    pub self_serve: Option<bool>,
+    /// Rejects airdrops to wallets that already received from this campaign
+    pub unique_recipients: Option<bool>,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
-    SetOperators {
-        operators: Vec<String>,
-        is_operators: Vec<bool>,
+    GrantRole {
+        role: Role,
+        account: String,
+    },
+    RevokeRole {
+        role: Role,
+        account: String,
     },
     SetMaxBatchSize {
         new_size: Uint64,
@@ -20,24 +54,95 @@ pub enum ExecuteMsg {
     SetFeePerBatch {
         new_fee: Uint128,
     },
-    CreateAirdropCampaign {
-        campaign_id: String,
-        assets: Vec<Asset>,
-        starting_time: Uint64,
-    },
-    UpdateCampaign {
-        campaign_id: String,
-        assets: Vec<Asset>,
-        starting_time: Uint64,
+    /// Sets the CW20 token accepted as airdrop fee, or stops accepting one with `None`
+    SetFeeToken {
+        fee_token: Option<String>,
     },
+    CreateAirdropCampaign(CampaignSettings),
+    UpdateCampaign(CampaignSettings),
+    /// Entries whose transfer fails are kept in the campaign and listed by
+    /// `ListFailedTransfers`; sending them again deducts the amount sent from the record.
     Airdrop {
         campaign_id: String,
         asset_indexes: Vec<Uint64>,
         recipients: Vec<String>,
+        /// Amount drawn from each asset; defaults to the whole remaining amount
+        amounts: Option<Vec<Uint128>>,
     },
     WithdrawAirdropFee {
         recipient: String,
     },
+    Claim {
+        campaign_id: String,
+        asset_index: Uint64,
+        amount: Uint128,
+        proof: Vec<String>,
+    },
+    /// Pays out a voucher signed by `pubkey` with `deps.api.secp256k1_verify` over
+    /// `voucher_digest`
+    ClaimWithVoucher {
+        voucher: Voucher,
+        pubkey: Binary,
+        signature: Binary,
+    },
+    RegisterVoucherSigner {
+        pubkey: Binary,
+    },
+    RevokeVoucherSigner {
+        pubkey: Binary,
+    },
+    CancelCampaign {
+        campaign_id: String,
+    },
+    CloseCampaign {
+        campaign_id: String,
+    },
+    SetPaused {
+        paused: bool,
+    },
+    SetCampaignPaused {
+        campaign_id: String,
+        paused: bool,
+    },
+    /// Lets the campaign creator grant or revoke `Airdrop` rights on this campaign only
+    SetCampaignDelegates {
+        campaign_id: String,
+        delegates: Vec<String>,
+        is_delegates: Vec<bool>,
+    },
+    WithdrawVested {
+        campaign_id: String,
+        asset_index: Uint64,
+    },
+    ProposeNewAdmin {
+        new_admin: String,
+        expiry: Option<Uint64>,
+    },
+    AcceptAdmin {},
+    RenounceAdmin {},
+    Receive(TokenReceiveMsg),
+    ReceiveNft(Cw721ReceiveMsg),
+    BatchReceive(Cw1155BatchReceiveMsg),
+}
+
+/// CW20 and CW1155 both call back into `receive`, so the payload is told apart by its fields.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum TokenReceiveMsg {
+    Cw20(Cw20ReceiveMsg),
+    Cw1155(Cw1155ReceiveMsg),
+}
+
+/// Message embedded in the `msg` field of token receive hooks
+#[cw_serde]
+pub enum ReceiveMsg {
+    FundCampaign {
+        campaign_id: String,
+    },
+    /// Sent with the platform fee token, which pays the airdrop fee
+    CreateAirdropCampaign(CampaignSettings),
+    /// Sent with the fee token of the campaign, which pays any increase of the airdrop fee
+    UpdateCampaign(CampaignSettings),
 }
 
 #[cw_serde]
@@ -48,4 +153,190 @@ pub enum QueryMsg {
 
     #[returns(AirdropCampaign)]
     GetCampaignById { campaign_id: String },
+
+    #[returns(CampaignsResponse)]
+    ListCampaigns {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        status: Option<CampaignStatus>,
+    },
+
+    #[returns(CampaignsResponse)]
+    CampaignsByCreator {
+        creator: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(RoleMembersResponse)]
+    ListRoleMembers {
+        role: Role,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(AccountRolesResponse)]
+    GetAccountRoles { account: String },
+
+    #[returns(VoucherSignersResponse)]
+    ListVoucherSigners {
+        start_after: Option<Binary>,
+        limit: Option<u32>,
+    },
+
+    #[returns(bool)]
+    IsVoucherNonceUsed { pubkey: Binary, nonce: Uint64 },
+
+    /// Whether `recipient` already received from a campaign with `unique_recipients`
+    #[returns(bool)]
+    HasReceived {
+        campaign_id: String,
+        recipient: String,
+    },
+
+    #[returns(DelegatesResponse)]
+    ListCampaignDelegates {
+        campaign_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(PlatformConfigResponse)]
+    GetPlatformConfig {},
+
+    #[returns(VestingStatus)]
+    GetVestingAllocation {
+        recipient: String,
+        campaign_id: String,
+        asset_index: Uint64,
+    },
+
+    #[returns(VestingAllocationsResponse)]
+    ListVestingAllocations {
+        recipient: String,
+        start_after: Option<(String, Uint64)>,
+        limit: Option<u32>,
+    },
+
+    #[returns(FailedTransfersResponse)]
+    ListFailedTransfers {
+        campaign_id: String,
+        /// `(recipient, asset_index)` of the last entry of the previous page
+        start_after: Option<(String, Uint64)>,
+        limit: Option<u32>,
+    },
+
+    #[returns(FeeBalancesResponse)]
+    GetFeeBalances {},
+
+    /// Dry run of `Airdrop`, also checking balances and approvals on the asset contracts
+    #[returns(SimulateAirdropResponse)]
+    SimulateAirdrop {
+        campaign_id: String,
+        asset_indexes: Vec<Uint64>,
+        recipients: Vec<String>,
+        amounts: Option<Vec<Uint128>>,
+    },
+
+    #[returns(Option<PendingAdmin>)]
+    GetPendingAdmin {},
+
+    /// Whether the Merkle leaf `(recipient, asset_index, amount)` has been claimed
+    #[returns(bool)]
+    IsClaimed {
+        campaign_id: String,
+        recipient: String,
+        asset_index: Uint64,
+        amount: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct CampaignsResponse {
+    pub campaigns: Vec<AirdropCampaign>,
+    /// `start_after` of the next page, `None` once the last campaign has been reached. Status
+    /// filters scan a bounded number of campaigns per page, so a page may hold fewer than
+    /// `limit` campaigns while more follow.
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct VoucherSignersResponse {
+    pub pubkeys: Vec<Binary>,
+}
+
+#[cw_serde]
+pub struct AccountRolesResponse {
+    /// Roles granted to the account. The admin holds every role without grants.
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct DelegatesResponse {
+    pub delegates: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct PlatformConfigResponse {
+    pub platform: AirdropPlatform,
+    pub paused: bool,
+    pub contract_version: ContractVersion,
+}
+
+#[cw_serde]
+pub struct VestingStatus {
+    pub campaign_id: String,
+    pub asset_index: Uint64,
+    pub allocation: VestingAllocation,
+    /// Unlocked so far, including withdrawn amounts
+    pub vested_amount: Uint128,
+    pub withdrawable_amount: Uint128,
+}
+
+#[cw_serde]
+pub struct VestingAllocationsResponse {
+    pub allocations: Vec<VestingStatus>,
+}
+
+#[cw_serde]
+pub struct FailedTransfersResponse {
+    pub failed_transfers: Vec<FailedTransfer>,
+}
+
+#[cw_serde]
+pub struct FeeBalancesResponse {
+    pub fee_balances: Vec<FeeAssetBalance>,
+    /// Native campaign assets held by this contract, which are never withdrawn as fee
+    pub escrowed_native: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct FeeAssetBalance {
+    /// `NATIVE_DENOM` or the address of a CW20 fee token
+    pub fee_asset: String,
+    /// Fees of executed batches, withdrawable by the admin
+    pub earned: Uint128,
+    /// Fees of batches not run yet, returned if the campaign is updated or cancelled
+    pub refundable: Uint128,
+}
+
+#[cw_serde]
+pub struct SimulateAirdropResponse {
+    /// Why the whole batch would be rejected. Entries are only reported when this is `None`.
+    pub error: Option<String>,
+    pub entries: Vec<SimulatedTransfer>,
+}
+
+#[cw_serde]
+pub struct SimulatedTransfer {
+    pub asset_index: Uint64,
+    pub recipient: String,
+    pub amount: Uint128,
+    /// Why the entry would be rejected or its transfer would fail
+    pub error: Option<String>,
 }
@@ -2,17 +2,137 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Addr, Uint128, Uint64};
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
-use crate::helpers::AirdropCampaign;
+use crate::helpers::{AirdropCampaign, AssetType, VestingSchedule};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct AirdropPlatform {
-    pub admin: Addr,
-    pub max_match_size: Uint64,
+    /// `None` once the admin has been renounced
+    pub admin: Option<Addr>,
+    pub max_batch_size: Uint64,
     pub fee_per_batch: Uint128,
+    /// CW20 token accepted as airdrop fee through `Send`, besides `NATIVE_DENOM`
+    #[serde(default)]
+    pub fee_token: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PendingAdmin {
+    pub new_admin: Addr,
+    /// Block time in seconds after which the proposal can no longer be accepted
+    pub expiry: Option<Uint64>,
+}
+
+/// Tokens locked in this contract for one recipient of a vesting asset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct VestingAllocation {
+    pub asset_type: AssetType,
+    pub asset_address: Addr,
+    pub asset_id: String,
+    pub schedule: VestingSchedule,
+    pub total_amount: Uint128,
+    pub withdrawn_amount: Uint128,
+}
+
+/// Transfers dispatched by one `Airdrop` call, kept until each of them has replied
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AirdropBatch {
+    /// Campaign as left by the batch, restored from here if the batch removed it
+    pub campaign: AirdropCampaign,
+    /// Entries of the batch, indexed by the entry index encoded in the reply ID
+    pub entries: Vec<BatchEntry>,
+    /// Transfers that have not replied yet
+    pub pending_replies: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BatchEntry {
+    pub asset_index: Uint64,
+    pub recipient: Addr,
+    pub amount: Uint128,
+}
+
+/// Airdrop fees held in one fee asset. A fee is earned once the batch it paid for has run and
+/// stays refundable to the campaign creator until then.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq, JsonSchema)]
+pub struct FeeBalance {
+    pub earned: Uint128,
+    pub refundable: Uint128,
+}
+
+/// Failed airdrop transfers of one asset to one recipient, with the error of the latest one.
+/// Their amount stays in the campaign and can be sent again with `Airdrop`, which deducts what
+/// it sends from the record.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FailedTransfer {
+    pub asset_index: Uint64,
+    pub recipient: Addr,
+    pub amount: Uint128,
+    pub error: String,
 }
 
 pub const AIRDROP_PLATFORM: Item<AirdropPlatform> = Item::new("airdrop_platform");
-pub const ALL_CAMPAIGNS: Map<String, AirdropCampaign> = Map::new("all_campaigns");
-pub const OPERATORS: Map<Addr, bool> = Map::new("operators");
+pub const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+/// Blocks airdrops and campaign creation or updates on the whole platform
+pub const PAUSED: Item<bool> = Item::new("paused");
+pub struct CampaignIndexes<'a> {
+    pub creator: MultiIndex<'a, Addr, AirdropCampaign, String>,
+}
+
+impl<'a> IndexList<AirdropCampaign> for CampaignIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AirdropCampaign>> + '_> {
+        let v: Vec<&dyn Index<AirdropCampaign>> = vec![&self.creator];
+        Box::new(v.into_iter())
+    }
+}
+
+/// All campaigns keyed by campaign ID, indexed by creator
+pub fn all_campaigns<'a>() -> IndexedMap<'a, String, AirdropCampaign, CampaignIndexes<'a>> {
+    let indexes = CampaignIndexes {
+        creator: MultiIndex::new(
+            |_pk, campaign| campaign.creator.clone(),
+            "all_campaigns",
+            "all_campaigns__creator",
+        ),
+    };
+    IndexedMap::new("all_campaigns", indexes)
+}
+
+/// Accounts holding each role besides the admin, keyed by (role key, account)
+pub const ROLES: Map<(&str, Addr), bool> = Map::new("roles");
+/// Accounts allowed to run `Airdrop` on a single campaign, keyed by (campaign instance,
+/// delegate)
+pub const CAMPAIGN_DELEGATES: Map<(u64, Addr), bool> = Map::new("campaign_delegates");
+/// Instance of the latest campaign created
+pub const CAMPAIGN_INSTANCES: Item<u64> = Item::new("campaign_instances");
+/// Claimed Merkle leaves keyed by (campaign instance, leaf hash), so one recipient can claim
+/// several leaves of the same asset
+pub const CLAIMED_LEAVES: Map<(u64, &[u8]), bool> = Map::new("claimed_leaf_hashes");
+/// Claims recorded up to 0.3.x, keyed by (campaign ID, recipient, asset index). Their amounts
+/// were not kept, so they cannot be rekeyed by leaf and are still checked by `Claim` for
+/// campaigns created before 0.4.0.
+pub const LEGACY_CLAIMS: Map<(String, Addr, u64), bool> = Map::new("claimed_leaves");
+/// Wallets that received from a campaign with `unique_recipients`, keyed by (campaign
+/// instance, recipient)
+pub const CAMPAIGN_RECIPIENTS: Map<(u64, Addr), bool> = Map::new("campaign_recipients");
+/// Compressed secp256k1 public keys whose vouchers are accepted by `ClaimWithVoucher`
+pub const VOUCHER_SIGNERS: Map<&[u8], bool> = Map::new("voucher_signers");
+/// Redeemed voucher nonces keyed by (compressed signer public key, nonce). Kept after the
+/// campaign is removed so vouchers cannot be replayed against a campaign reusing its ID.
+pub const USED_NONCES: Map<(&[u8], u64), bool> = Map::new("used_nonces");
+pub const ESCROW_DEPOSITS: Map<(String, u64), Uint128> = Map::new("escrow_deposits");
+/// Airdrop fees held by this contract, keyed by fee asset: `NATIVE_DENOM` or the address of
+/// the CW20 fee token
+pub const FEE_BALANCES: Map<String, FeeBalance> = Map::new("fee_balances");
+/// Native campaign assets held by this contract, per denom. Never swept as airdrop fee.
+pub const ESCROWED_NATIVE: Map<String, Uint128> = Map::new("escrowed_native");
+/// Vesting allocations keyed by (recipient, campaign ID, asset index)
+pub const VESTING_ALLOCATIONS: Map<(Addr, String, u64), VestingAllocation> =
+    Map::new("vesting_allocations");
+/// ID of the latest `Airdrop` batch. Transfers may call back into this contract and run
+/// batches of their own, so each batch is stored under its own ID until all its replies are in.
+pub const BATCH_COUNTER: Item<u64> = Item::new("batch_counter");
+pub const AIRDROP_BATCHES: Map<u64, AirdropBatch> = Map::new("airdrop_batches");
+/// Failed airdrop transfers keyed by (campaign instance, recipient, asset index)
+pub const FAILED_TRANSFERS: Map<(u64, Addr, u64), FailedTransfer> = Map::new("failed_transfers");
@@ -1,11 +1,19 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        helpers::{AirdropCampaign, Asset, AssetType, NATIVE_DENOM},
-        msg::{ExecuteMsg, QueryMsg},
+        helpers::{
+            merkle_leaf, AirdropCampaign, Asset, AssetType, CampaignStatus, Role, NATIVE_DENOM,
+        },
+        msg::{CampaignSettings, CampaignsResponse, ExecuteMsg, QueryMsg, ReceiveMsg},
+        state::PendingAdmin,
+        PlaylinkAirdropErr,
     };
-    use cosmwasm_std::{coins, Addr, BlockInfo, Coin, Empty, Timestamp, Uint128, Uint64};
-    use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+    use cosmwasm_std::{
+        coin, coins, to_binary, Addr, BlockInfo, Coin, Empty, Timestamp, Uint128, Uint64,
+    };
+    use cw_multi_test::{App, AppBuilder, AppResponse, ContractWrapper, Executor};
+    use serde::Serialize;
+    use sha2::{Digest, Sha256};
 
     const ADMIN: &str = "cosmos10w2pwzxaacsj508ma5ruz5wnhn83tld73shr4a";
     const OPERATOR: &str = "cosmos10w2pwzxaacsj508ma5ruz5wnhn83tld73shr4b";
@@ -13,17 +21,18 @@ mod tests {
     const WINNER_1: &str = "cosmos10w2pwzxaacsj508ma5ruz5wnhn83tld73shr4d";
     const WINNER_2: &str = "cosmos10w2pwzxaacsj508ma5ruz5wnhn83tld73shr4e";
     const CAMPAIGN_ID: &str = "01BX5ZZKBKACTAV9WEVGEMMVRY";
+    const IBC_DENOM: &str = "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
 
     fn mock_blockchain() -> App {
         AppBuilder::new().build(|router, _, storage| {
-            let accounts = vec![ADMIN, OPERATOR, CAMPAIGN_CREATOR];
+            let accounts = [ADMIN, OPERATOR, CAMPAIGN_CREATOR];
             for account in accounts.iter() {
                 router
                     .bank
                     .init_balance(
                         storage,
                         &Addr::unchecked(*account),
-                        coins(1000, NATIVE_DENOM),
+                        vec![coin(1000, NATIVE_DENOM), coin(1000, IBC_DENOM)],
                     )
                     .unwrap();
             }
@@ -47,11 +56,14 @@ mod tests {
             cw1155_base::contract::instantiate,
             cw1155_base::contract::query,
         )));
-        let airdrop_id = blockchain.store_code(Box::new(ContractWrapper::new(
-            crate::contract::execute,
-            crate::contract::instantiate,
-            crate::contract::query,
-        )));
+        let airdrop_id = blockchain.store_code(Box::new(
+            ContractWrapper::new(
+                crate::contract::execute,
+                crate::contract::instantiate,
+                crate::contract::query,
+            )
+            .with_reply(crate::contract::reply),
+        ));
 
         let cw20_address = blockchain
             .instantiate_contract(
@@ -125,6 +137,77 @@ mod tests {
         )
     }
 
+    fn grant_role(blockchain: &mut App, airdrop_address: &Addr, role: Role, account: &str) {
+        blockchain
+            .execute_contract(
+                Addr::unchecked(ADMIN),
+                airdrop_address.clone(),
+                &ExecuteMsg::GrantRole {
+                    role,
+                    account: String::from(account),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+
+    fn set_up_operator(blockchain: &mut App, airdrop_address: &Addr) {
+        for role in [
+            Role::AirdropExecutor,
+            Role::BatchSizeManager,
+            Role::FeeManager,
+        ] {
+            grant_role(blockchain, airdrop_address, role, OPERATOR);
+        }
+    }
+
+    fn fast_forward(blockchain: &mut App, seconds: u64) {
+        let current_block = blockchain.block_info();
+        blockchain.set_block(BlockInfo {
+            height: current_block.height + 1,
+            time: Timestamp::from_seconds(current_block.time.seconds() + seconds),
+            chain_id: current_block.chain_id,
+        });
+    }
+
+    /// Executes `msg` on `contract` as `sender`, with the error downcast to `PlaylinkAirdropErr`
+    fn execute_as<T: Serialize + std::fmt::Debug>(
+        blockchain: &mut App,
+        contract: &Addr,
+        sender: &str,
+        msg: &T,
+        funds: &[Coin],
+    ) -> Result<AppResponse, PlaylinkAirdropErr> {
+        blockchain
+            .execute_contract(Addr::unchecked(sender), contract.clone(), msg, funds)
+            .map_err(|err| err.downcast::<PlaylinkAirdropErr>().unwrap())
+    }
+
+    fn get_campaign(blockchain: &App, airdrop_address: &Addr) -> AirdropCampaign {
+        blockchain
+            .wrap()
+            .query_wasm_smart(
+                airdrop_address.clone(),
+                &QueryMsg::GetCampaignById {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                },
+            )
+            .unwrap()
+    }
+
+    fn cw20_balance(blockchain: &App, cw20_address: &Addr, address: &str) -> u128 {
+        let balance: cw20::BalanceResponse = blockchain
+            .wrap()
+            .query_wasm_smart(
+                cw20_address.clone(),
+                &cw20::Cw20QueryMsg::Balance {
+                    address: String::from(address),
+                },
+            )
+            .unwrap();
+        balance.balance.u128()
+    }
+
     mod playlink_airdrop {
         use super::*;
 
@@ -179,17 +262,7 @@ mod tests {
                 .unwrap();
 
             /* ================= Set up operators ================= */
-            blockchain
-                .execute_contract(
-                    Addr::unchecked(ADMIN),
-                    airdrop_address.clone(),
-                    &ExecuteMsg::SetOperators {
-                        operators: vec![String::from(Addr::unchecked(OPERATOR))],
-                        is_operators: vec![true],
-                    },
-                    &[],
-                )
-                .unwrap();
+            set_up_operator(&mut blockchain, &airdrop_address);
 
             /* ================= Update max batch size ================= */
             blockchain
@@ -222,7 +295,7 @@ mod tests {
                 .execute_contract(
                     Addr::unchecked(CAMPAIGN_CREATOR),
                     airdrop_address.clone(),
-                    &ExecuteMsg::CreateAirdropCampaign {
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
                         campaign_id: String::from(CAMPAIGN_ID),
                         assets: vec![
                             Asset {
@@ -230,22 +303,31 @@ mod tests {
                                 asset_address: cw20_address.clone(),
                                 asset_id: String::from(""),
                                 available_amount: Uint128::from(100_u128),
+                                vesting: None,
                             },
                             Asset {
                                 asset_type: AssetType::CW20,
                                 asset_address: cw20_address.clone(),
                                 asset_id: String::from(""),
                                 available_amount: Uint128::from(150_u128),
+                                vesting: None,
                             },
                             Asset {
                                 asset_type: AssetType::CW721,
                                 asset_address: cw721_address.clone(),
                                 asset_id: String::from("1234"),
                                 available_amount: Uint128::one(),
+                                vesting: None,
                             },
                         ],
                         starting_time: campaign_starting_time,
-                    },
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
                     &[Coin {
                         amount: Uint128::from(20_u128),
                         denom: String::from(NATIVE_DENOM),
@@ -265,7 +347,7 @@ mod tests {
             assert_eq!(campaign.campaign_id, CAMPAIGN_ID);
             assert_eq!(campaign.creator, Addr::unchecked(CAMPAIGN_CREATOR));
             assert_eq!(campaign.assets.len(), 3);
-            assert_eq!(campaign.assets.get(0).unwrap().asset_address, cw20_address);
+            assert_eq!(campaign.assets.first().unwrap().asset_address, cw20_address);
             assert_eq!(campaign.assets.get(2).unwrap().asset_address, cw721_address);
             assert_eq!(campaign.max_batch_size.u64(), 3);
             assert_eq!(campaign.starting_time, campaign_starting_time);
@@ -278,7 +360,7 @@ mod tests {
                 .execute_contract(
                     Addr::unchecked(CAMPAIGN_CREATOR),
                     airdrop_address.clone(),
-                    &ExecuteMsg::UpdateCampaign {
+                    &ExecuteMsg::UpdateCampaign(CampaignSettings {
                         campaign_id: String::from(CAMPAIGN_ID),
                         assets: vec![
                             Asset {
@@ -286,34 +368,45 @@ mod tests {
                                 asset_address: cw721_address.clone(),
                                 asset_id: String::from("9999"),
                                 available_amount: Uint128::one(),
+                                vesting: None,
                             },
                             Asset {
                                 asset_type: AssetType::CW20,
                                 asset_address: cw20_address.clone(),
                                 asset_id: String::from(""),
                                 available_amount: Uint128::from(180_u128),
+                                vesting: None,
                             },
                             Asset {
                                 asset_type: AssetType::CW20,
                                 asset_address: cw20_address.clone(),
                                 asset_id: String::from(""),
                                 available_amount: Uint128::from(100_u128),
+                                vesting: None,
                             },
                             Asset {
                                 asset_type: AssetType::CW721,
                                 asset_address: cw721_address.clone(),
                                 asset_id: String::from("8888"),
                                 available_amount: Uint128::one(),
+                                vesting: None,
                             },
                             Asset {
                                 asset_type: AssetType::CW1155,
                                 asset_address: cw1155_address.clone(),
                                 asset_id: String::from("1234"),
                                 available_amount: Uint128::from(15_u128),
+                                vesting: None,
                             },
                         ],
                         starting_time: campaign_starting_time,
-                    },
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
                     &[Coin {
                         amount: Uint128::from(25_u128),
                         denom: String::from(NATIVE_DENOM),
@@ -333,7 +426,10 @@ mod tests {
             assert_eq!(campaign.campaign_id, CAMPAIGN_ID);
             assert_eq!(campaign.creator, Addr::unchecked(CAMPAIGN_CREATOR));
             assert_eq!(campaign.assets.len(), 5);
-            assert_eq!(campaign.assets.get(0).unwrap().asset_address, cw721_address);
+            assert_eq!(
+                campaign.assets.first().unwrap().asset_address,
+                cw721_address
+            );
             assert_eq!(campaign.assets.get(2).unwrap().asset_address, cw20_address);
             assert_eq!(campaign.max_batch_size.u64(), 3);
             assert_eq!(campaign.starting_time, campaign_starting_time);
@@ -401,12 +497,7 @@ mod tests {
             assert_eq!(allowance.allowance, Uint128::from(280_u128));
 
             /* ================= Airdrop CW20 assets ================= */
-            let current_block = blockchain.block_info();
-            blockchain.set_block(BlockInfo {
-                height: current_block.height + 1,
-                time: Timestamp::from_seconds(current_block.time.seconds() + 10 * 60), // Forward blockchain clock by 10 minute
-                chain_id: current_block.chain_id,
-            });
+            fast_forward(&mut blockchain, 10 * 60);
             blockchain
                 .execute_contract(
                     Addr::unchecked(OPERATOR),
@@ -418,6 +509,7 @@ mod tests {
                             String::from(Addr::unchecked(WINNER_1)),
                             String::from(Addr::unchecked(WINNER_2)),
                         ],
+                        amounts: None,
                     },
                     &[],
                 )
@@ -452,7 +544,7 @@ mod tests {
             assert_eq!(winner1_cw20_balance.balance.u128(), 180);
             assert_eq!(winner2_cw20_balance.balance.u128(), 100);
             assert_eq!(campaign.total_available_assets.u128(), 17);
-            assert_eq!(campaign.assets.get(0).unwrap().available_amount.u128(), 1);
+            assert_eq!(campaign.assets.first().unwrap().available_amount.u128(), 1);
             assert_eq!(campaign.assets.get(1).unwrap().available_amount.u128(), 0);
             assert_eq!(campaign.assets.get(2).unwrap().available_amount.u128(), 0);
             assert_eq!(campaign.assets.get(3).unwrap().available_amount.u128(), 1);
@@ -475,6 +567,7 @@ mod tests {
                             String::from(Addr::unchecked(WINNER_2)),
                             String::from(Addr::unchecked(WINNER_1)),
                         ],
+                        amounts: None,
                     },
                     &[],
                 )
@@ -545,4 +638,5374 @@ mod tests {
             assert_eq!(contract_balance2.amount.u128(), 0);
         }
     }
+
+    mod merkle_claim {
+        use super::*;
+
+        pub(super) fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+            let (first, second) = if a <= b { (a, b) } else { (b, a) };
+            let mut hasher = Sha256::new();
+            hasher.update(first);
+            hasher.update(second);
+            hasher.finalize().into()
+        }
+
+        #[test]
+        fn merkle_claim_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+
+            /* ================= Build a two-leaf Merkle tree ================= */
+            let leaf1 = merkle_leaf(WINNER_1, Uint64::zero(), Uint128::from(60_u128));
+            let leaf2 = merkle_leaf(WINNER_2, Uint64::zero(), Uint128::from(40_u128));
+            let merkle_root = hex::encode(hash_pair(leaf1, leaf2));
+
+            /* ================= Create a claimable campaign ================= */
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![Asset {
+                            asset_type: AssetType::CW20,
+                            asset_address: cw20_address.clone(),
+                            asset_id: String::from(""),
+                            available_amount: Uint128::from(100_u128),
+                            vesting: None,
+                        }],
+                        starting_time: campaign_starting_time,
+                        merkle_root: Some(merkle_root),
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(100_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Claims are rejected before the campaign starts ================= */
+            let claim_winner1 = ExecuteMsg::Claim {
+                campaign_id: String::from(CAMPAIGN_ID),
+                asset_index: Uint64::zero(),
+                amount: Uint128::from(60_u128),
+                proof: vec![hex::encode(leaf2)],
+            };
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_1),
+                    airdrop_address.clone(),
+                    &claim_winner1,
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::CampaignNotStarts {
+                    campaign_id: String::from(CAMPAIGN_ID)
+                }
+            );
+
+            fast_forward(&mut blockchain, 30 * 60);
+
+            /* ================= Operators cannot push a claimable campaign ================= */
+            set_up_operator(&mut blockchain, &airdrop_address);
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero()],
+                        recipients: vec![String::from(WINNER_1)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::ClaimOnlyCampaign {
+                    campaign_id: String::from(CAMPAIGN_ID)
+                }
+            );
+
+            /* ================= Winner 1 claims once ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_1),
+                    airdrop_address.clone(),
+                    &claim_winner1,
+                    &[],
+                )
+                .unwrap();
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_1),
+                    airdrop_address.clone(),
+                    &claim_winner1,
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::AlreadyClaimed {
+                    index: Uint64::zero()
+                }
+            );
+            let claimed: bool = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::IsClaimed {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        recipient: String::from(WINNER_1),
+                        asset_index: Uint64::zero(),
+                        amount: Uint128::from(60_u128),
+                    },
+                )
+                .unwrap();
+            assert!(claimed);
+            let campaign: AirdropCampaign = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .unwrap();
+            assert_eq!(campaign.total_available_assets.u128(), 40);
+
+            /* ================= Winner 2 cannot inflate the claimed amount ================= */
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_2),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Claim {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index: Uint64::zero(),
+                        amount: Uint128::from(41_u128),
+                        proof: vec![hex::encode(leaf1)],
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::InvalidMerkleProof {}
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_2),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Claim {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index: Uint64::zero(),
+                        amount: Uint128::from(40_u128),
+                        proof: vec![hex::encode(leaf1)],
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            let winner1_balance: cw20::BalanceResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    cw20_address.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: String::from(WINNER_1),
+                    },
+                )
+                .unwrap();
+            let winner2_balance: cw20::BalanceResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    cw20_address,
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: String::from(WINNER_2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(winner1_balance.balance.u128(), 60);
+            assert_eq!(winner2_balance.balance.u128(), 40);
+            blockchain
+                .wrap()
+                .query_wasm_smart::<AirdropCampaign>(
+                    airdrop_address,
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .unwrap_err(); // Fully claimed campaigns are removed
+        }
+
+        #[test]
+        fn claims_are_keyed_by_leaf_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+
+            /* ================= Winner 1 holds two leaves of the same asset ================= */
+            let leaf1 = merkle_leaf(WINNER_1, Uint64::zero(), Uint128::from(60_u128));
+            let leaf2 = merkle_leaf(WINNER_1, Uint64::zero(), Uint128::from(40_u128));
+            let create = |blockchain: &mut App| {
+                let create_msg = ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    assets: vec![Asset {
+                        asset_type: AssetType::Native,
+                        asset_address: Addr::unchecked(""),
+                        asset_id: String::from(NATIVE_DENOM),
+                        available_amount: Uint128::from(100_u128),
+                        vesting: None,
+                    }],
+                    starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                    merkle_root: Some(hex::encode(hash_pair(leaf1, leaf2))),
+                    escrow: None,
+                    ending_time: None,
+                    strict: None,
+                    self_serve: None,
+                    unique_recipients: None,
+                });
+                blockchain
+                    .execute_contract(
+                        Addr::unchecked(CAMPAIGN_CREATOR),
+                        airdrop_address.clone(),
+                        &create_msg,
+                        &coins(103, NATIVE_DENOM),
+                    )
+                    .unwrap();
+            };
+            let claim = |blockchain: &mut App, amount: u128, sibling: [u8; 32]| {
+                execute_as(
+                    blockchain,
+                    &airdrop_address,
+                    WINNER_1,
+                    &ExecuteMsg::Claim {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index: Uint64::zero(),
+                        amount: Uint128::from(amount),
+                        proof: vec![hex::encode(sibling)],
+                    },
+                    &[],
+                )
+            };
+            create(&mut blockchain);
+            fast_forward(&mut blockchain, 60);
+            claim(&mut blockchain, 60, leaf2).unwrap();
+            assert_eq!(
+                claim(&mut blockchain, 60, leaf2).unwrap_err(),
+                PlaylinkAirdropErr::AlreadyClaimed {
+                    index: Uint64::zero()
+                }
+            );
+            claim(&mut blockchain, 40, leaf1).unwrap();
+
+            /* ================= Claims do not outlive their campaign ================= */
+            create(&mut blockchain);
+            let claimed: bool = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::IsClaimed {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        recipient: String::from(WINNER_1),
+                        asset_index: Uint64::zero(),
+                        amount: Uint128::from(60_u128),
+                    },
+                )
+                .unwrap();
+            assert!(!claimed);
+            fast_forward(&mut blockchain, 60);
+            claim(&mut blockchain, 60, leaf2).unwrap();
+            let winner_balance = blockchain
+                .wrap()
+                .query_balance(WINNER_1, NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(winner_balance.amount.u128(), 160);
+        }
+    }
+
+    mod escrow {
+        use super::*;
+
+        #[test]
+        fn escrow_campaign_test() {
+            let (mut blockchain, cw20_address, cw721_address, cw1155_address, airdrop_address) =
+                proper_instantiate();
+
+            /* ================= Mint some CW721 and CW1155 tokens ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw721_address.clone(),
+                    &cw721_base::msg::ExecuteMsg::<cw721_base::Extension, Empty>::Mint(
+                        cw721_base::MintMsg::<cw721_base::Extension> {
+                            token_id: String::from("8888"),
+                            owner: String::from(CAMPAIGN_CREATOR),
+                            token_uri: None,
+                            extension: None,
+                        },
+                    ),
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw1155_address.clone(),
+                    &cw1155::Cw1155ExecuteMsg::Mint {
+                        to: String::from(CAMPAIGN_CREATOR),
+                        token_id: String::from("1234"),
+                        value: Uint128::from(15_u128),
+                        msg: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            /* ================= Create an escrow campaign ================= */
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: cw20_address.clone(),
+                                asset_id: String::from(""),
+                                available_amount: Uint128::from(100_u128),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: cw20_address.clone(),
+                                asset_id: String::from(""),
+                                available_amount: Uint128::from(50_u128),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::CW721,
+                                asset_address: cw721_address.clone(),
+                                asset_id: String::from("8888"),
+                                available_amount: Uint128::one(),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::CW1155,
+                                asset_address: cw1155_address.clone(),
+                                asset_id: String::from("1234"),
+                                available_amount: Uint128::from(15_u128),
+                                vesting: None,
+                            },
+                        ],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: Some(true),
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(6, NATIVE_DENOM),
+                )
+                .unwrap();
+            let fund_msg = to_binary(&ReceiveMsg::FundCampaign {
+                campaign_id: String::from(CAMPAIGN_ID),
+            })
+            .unwrap();
+
+            /* ================= Deposits beyond the declared assets are rejected ================= */
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::Send {
+                        contract: airdrop_address.clone().into(),
+                        amount: Uint128::from(151_u128),
+                        msg: fund_msg.clone(),
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::ExcessEscrowDeposit {
+                    excess: Uint128::one()
+                }
+            );
+
+            /* ================= Deposit every asset ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::Send {
+                        contract: airdrop_address.clone().into(),
+                        amount: Uint128::from(150_u128),
+                        msg: fund_msg.clone(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw721_address.clone(),
+                    &cw721::Cw721ExecuteMsg::SendNft {
+                        contract: airdrop_address.clone().into(),
+                        token_id: String::from("8888"),
+                        msg: fund_msg.clone(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let mut campaign: AirdropCampaign = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .unwrap();
+            assert!(campaign.escrow);
+            assert!(!campaign.funded);
+
+            fast_forward(&mut blockchain, 30 * 60);
+            let airdrop_msg = ExecuteMsg::Airdrop {
+                campaign_id: String::from(CAMPAIGN_ID),
+                asset_indexes: vec![
+                    Uint64::zero(),
+                    Uint64::one(),
+                    Uint64::from(2_u64),
+                    Uint64::from(3_u64),
+                ],
+                recipients: vec![
+                    String::from(WINNER_1),
+                    String::from(WINNER_2),
+                    String::from(WINNER_1),
+                    String::from(WINNER_2),
+                ],
+                amounts: None,
+            };
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &airdrop_msg,
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::CampaignNotFunded {
+                    campaign_id: String::from(CAMPAIGN_ID)
+                }
+            );
+
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw1155_address.clone(),
+                    &cw1155::Cw1155ExecuteMsg::SendFrom {
+                        from: String::from(CAMPAIGN_CREATOR),
+                        to: airdrop_address.clone().into(),
+                        token_id: String::from("1234"),
+                        value: Uint128::from(15_u128),
+                        msg: Some(fund_msg),
+                    },
+                    &[],
+                )
+                .unwrap();
+            campaign = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .unwrap();
+            assert!(campaign.funded);
+
+            /* ================= Airdrop from the contract's own balance ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &airdrop_msg,
+                    &[],
+                )
+                .unwrap();
+            let winner1_cw20_balance: cw20::BalanceResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    cw20_address.clone(),
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: String::from(WINNER_1),
+                    },
+                )
+                .unwrap();
+            let contract_cw20_balance: cw20::BalanceResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    cw20_address,
+                    &cw20::Cw20QueryMsg::Balance {
+                        address: airdrop_address.clone().into(),
+                    },
+                )
+                .unwrap();
+            let cw721_owner: cw721::OwnerOfResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    cw721_address,
+                    &cw721::Cw721QueryMsg::OwnerOf {
+                        token_id: String::from("8888"),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+            let cw1155_balance: cw1155::BalanceResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    cw1155_address,
+                    &cw1155::Cw1155QueryMsg::Balance {
+                        owner: String::from(WINNER_2),
+                        token_id: String::from("1234"),
+                    },
+                )
+                .unwrap();
+            assert_eq!(winner1_cw20_balance.balance.u128(), 100);
+            assert_eq!(contract_cw20_balance.balance.u128(), 0);
+            assert_eq!(cw721_owner.owner, WINNER_1);
+            assert_eq!(cw1155_balance.balance.u128(), 15);
+            blockchain
+                .wrap()
+                .query_wasm_smart::<AirdropCampaign>(
+                    airdrop_address,
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .unwrap_err();
+        }
+    }
+
+    mod split_amounts {
+        use super::*;
+
+        #[test]
+        fn split_fungible_asset_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            /* ================= Create a campaign with a single CW20 pool ================= */
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![Asset {
+                            asset_type: AssetType::CW20,
+                            asset_address: cw20_address.clone(),
+                            asset_id: String::from(""),
+                            available_amount: Uint128::from(1000_u128),
+                            vesting: None,
+                        }],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(1000_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 30 * 60);
+
+            /* ================= Draw the pool down across several recipients ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::zero(), Uint64::zero()],
+                        recipients: vec![
+                            String::from(WINNER_1),
+                            String::from(WINNER_2),
+                            String::from(WINNER_1),
+                        ],
+                        amounts: Some(vec![
+                            Uint128::from(100_u128),
+                            Uint128::from(250_u128),
+                            Uint128::from(50_u128),
+                        ]),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 150);
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_2), 250);
+            assert_eq!(campaign.assets[0].available_amount.u128(), 600);
+            assert_eq!(campaign.total_available_assets.u128(), 600);
+
+            /* ================= Over-allocation within a batch is rejected ================= */
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::zero()],
+                        recipients: vec![String::from(WINNER_1), String::from(WINNER_2)],
+                        amounts: Some(vec![Uint128::from(500_u128), Uint128::from(101_u128)]),
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::AssetAmountExceeded {
+                    index: Uint64::zero(),
+                    available: Uint128::from(100_u128),
+                }
+            );
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero()],
+                        recipients: vec![String::from(WINNER_1)],
+                        amounts: Some(vec![]),
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::LengthMismatch {}
+            );
+
+            /* ================= Omitted amounts send the remainder ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero()],
+                        recipients: vec![String::from(WINNER_2)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_2), 850);
+            blockchain
+                .wrap()
+                .query_wasm_smart::<AirdropCampaign>(
+                    airdrop_address,
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .unwrap_err();
+        }
+    }
+
+    mod native_assets {
+        use super::*;
+
+        #[test]
+        fn native_asset_campaign_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            /* ================= Fund native assets together with the fee ================= */
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            let native_asset = |denom: &str, amount: u128| Asset {
+                asset_type: AssetType::Native,
+                asset_address: Addr::unchecked(""),
+                asset_id: String::from(denom),
+                available_amount: Uint128::from(amount),
+                vesting: None,
+            };
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset(NATIVE_DENOM, 300), native_asset(IBC_DENOM, 50)],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &[coin(302, NATIVE_DENOM), coin(50, IBC_DENOM)],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::InsufficientNativeAsset {
+                    amount: Uint128::from(300_u128),
+                    denom: String::from(NATIVE_DENOM),
+                }
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset(NATIVE_DENOM, 300), native_asset(IBC_DENOM, 50)],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &[coin(310, NATIVE_DENOM), coin(60, IBC_DENOM)],
+                )
+                .unwrap();
+            let creator_balances = blockchain
+                .wrap()
+                .query_all_balances(CAMPAIGN_CREATOR)
+                .unwrap();
+            assert_eq!(
+                creator_balances,
+                vec![coin(697, NATIVE_DENOM), coin(950, IBC_DENOM)]
+            );
+
+            /* ================= Fee withdrawal leaves native assets and unearned fees in place ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::WithdrawAirdropFee {
+                        recipient: String::from(ADMIN),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let contract_balance = blockchain
+                .wrap()
+                .query_balance(airdrop_address.clone(), NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(contract_balance.amount.u128(), 303);
+
+            /* ================= Lowering a native asset refunds the difference ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::UpdateCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset(NATIVE_DENOM, 200), native_asset(IBC_DENOM, 50)],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &[],
+                )
+                .unwrap();
+            let creator_balance = blockchain
+                .wrap()
+                .query_balance(CAMPAIGN_CREATOR, NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(creator_balance.amount.u128(), 797);
+
+            /* ================= Airdrop native coins with bank sends ================= */
+            fast_forward(&mut blockchain, 30 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::zero(), Uint64::one()],
+                        recipients: vec![
+                            String::from(WINNER_1),
+                            String::from(WINNER_2),
+                            String::from(WINNER_2),
+                        ],
+                        amounts: Some(vec![
+                            Uint128::from(120_u128),
+                            Uint128::from(80_u128),
+                            Uint128::from(50_u128),
+                        ]),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let winner1_balances = blockchain.wrap().query_all_balances(WINNER_1).unwrap();
+            let winner2_balances = blockchain.wrap().query_all_balances(WINNER_2).unwrap();
+            let contract_balances = blockchain
+                .wrap()
+                .query_all_balances(airdrop_address.clone())
+                .unwrap();
+            assert_eq!(winner1_balances, vec![coin(120, NATIVE_DENOM)]);
+            assert_eq!(
+                winner2_balances,
+                vec![coin(80, NATIVE_DENOM), coin(50, IBC_DENOM)]
+            );
+            assert_eq!(contract_balances, vec![coin(3, NATIVE_DENOM)]);
+
+            /* ================= The fee is earned once the batch has run ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::WithdrawAirdropFee {
+                        recipient: String::from(ADMIN),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let contract_balances = blockchain
+                .wrap()
+                .query_all_balances(airdrop_address)
+                .unwrap();
+            assert!(contract_balances.is_empty());
+        }
+    }
+
+    mod cancel_campaign {
+        use super::*;
+
+        #[test]
+        fn cancel_before_start_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+
+            /* ================= Create a partially funded escrow campaign ================= */
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: cw20_address.clone(),
+                                asset_id: String::from(""),
+                                available_amount: Uint128::from(150_u128),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::Native,
+                                asset_address: Addr::unchecked(""),
+                                asset_id: String::from(NATIVE_DENOM),
+                                available_amount: Uint128::from(100_u128),
+                                vesting: None,
+                            },
+                        ],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: Some(true),
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(103, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::Send {
+                        contract: airdrop_address.clone().into(),
+                        amount: Uint128::from(100_u128),
+                        msg: to_binary(&ReceiveMsg::FundCampaign {
+                            campaign_id: String::from(CAMPAIGN_ID),
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                cw20_balance(&blockchain, &cw20_address, CAMPAIGN_CREATOR),
+                900
+            );
+
+            /* ================= Only the creator can cancel ================= */
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::NotCampaignCreator {
+                    campaign_creator: String::from(CAMPAIGN_CREATOR)
+                }
+            );
+
+            /* ================= Cancel refunds the fee and every held asset ================= */
+            let res = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let event = res
+                .events
+                .iter()
+                .find(|event| event.ty == "wasm-playlink_airdrop")
+                .unwrap();
+            assert!(event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "refunded_fee" && attr.value == "3"));
+            assert_eq!(
+                cw20_balance(&blockchain, &cw20_address, CAMPAIGN_CREATOR),
+                1000
+            );
+            let creator_balance = blockchain
+                .wrap()
+                .query_balance(CAMPAIGN_CREATOR, NATIVE_DENOM)
+                .unwrap();
+            let contract_balance = blockchain
+                .wrap()
+                .query_balance(airdrop_address.clone(), NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(creator_balance.amount.u128(), 1000);
+            assert_eq!(contract_balance.amount.u128(), 0);
+            blockchain
+                .wrap()
+                .query_wasm_smart::<AirdropCampaign>(
+                    airdrop_address,
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .unwrap_err();
+        }
+
+        #[test]
+        fn cancel_after_start_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::SetMaxBatchSize {
+                        new_size: Uint64::from(2_u64),
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Create a campaign paying for two batches ================= */
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            let cw20_asset = Asset {
+                asset_type: AssetType::CW20,
+                asset_address: cw20_address.clone(),
+                asset_id: String::from(""),
+                available_amount: Uint128::from(10_u128),
+                vesting: None,
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![cw20_asset; 4],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(6, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(40_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Run one of the two batches ================= */
+            fast_forward(&mut blockchain, 30 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::one()],
+                        recipients: vec![String::from(WINNER_1), String::from(WINNER_2)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                get_campaign(&blockchain, &airdrop_address)
+                    .executed_batches
+                    .u64(),
+                1
+            );
+
+            /* ================= Cancel refunds the fee of the remaining batch ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let creator_balance = blockchain
+                .wrap()
+                .query_balance(CAMPAIGN_CREATOR, NATIVE_DENOM)
+                .unwrap();
+            let contract_balance = blockchain
+                .wrap()
+                .query_balance(airdrop_address, NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(creator_balance.amount.u128(), 997);
+            assert_eq!(contract_balance.amount.u128(), 3);
+        }
+    }
+
+    mod migration {
+        use super::*;
+        use crate::{
+            migrations::legacy::{AirdropPlatformV0_1, AIRDROP_PLATFORM_V0_1, OPERATORS_V0_3},
+            msg::{AccountRolesResponse, FeeAssetBalance, FeeBalancesResponse, MigrateMsg},
+            state::{all_campaigns, AirdropPlatform, AIRDROP_PLATFORM, LEGACY_CLAIMS},
+        };
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::{
+            from_slice, to_vec, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+            StdResult,
+        };
+        use cw_storage_plus::Map;
+
+        const REMOVED_CAMPAIGN_ID: &str = "01BX5ZZKBKACTAV9WEVGEMMVRX";
+
+        #[cw_serde]
+        struct LegacyInstantiateMsg {
+            contract: String,
+            version: String,
+        }
+
+        /// Campaign layout as stored up to 0.1.x
+        #[cw_serde]
+        struct AirdropCampaignV0_1 {
+            campaign_id: String,
+            creator: Addr,
+            assets: Vec<Asset>,
+            max_batch_size: Uint64,
+            starting_time: Uint64,
+            total_available_assets: Uint128,
+            airdrop_fee: Uint128,
+        }
+
+        /// Stands in for an already deployed contract by writing the storage layout of
+        /// `msg.version`: the 0.1.x layout, or the 0.3.x one with indexed campaigns
+        fn legacy_instantiate(
+            deps: DepsMut,
+            env: Env,
+            info: MessageInfo,
+            msg: LegacyInstantiateMsg,
+        ) -> StdResult<Response> {
+            let campaign = AirdropCampaignV0_1 {
+                campaign_id: String::from(CAMPAIGN_ID),
+                creator: Addr::unchecked(CAMPAIGN_CREATOR),
+                assets: vec![Asset {
+                    asset_type: AssetType::CW20,
+                    asset_address: Addr::unchecked("cw20"),
+                    asset_id: String::from(""),
+                    available_amount: Uint128::from(100_u128),
+                    vesting: None,
+                }],
+                max_batch_size: Uint64::from(7_u64),
+                starting_time: Uint64::from(env.block.time.seconds() + 60),
+                total_available_assets: Uint128::from(100_u128),
+                airdrop_fee: Uint128::from(3_u128),
+            };
+            if msg.version.starts_with("0.3.") {
+                AIRDROP_PLATFORM.save(
+                    deps.storage,
+                    &AirdropPlatform {
+                        admin: Some(info.sender),
+                        max_batch_size: Uint64::from(7_u64),
+                        fee_per_batch: Uint128::from(3_u128),
+                        fee_token: None,
+                    },
+                )?;
+                let campaign: AirdropCampaign = from_slice(&to_vec(&campaign)?)?;
+                all_campaigns().save(deps.storage, String::from(CAMPAIGN_ID), &campaign)?;
+            } else {
+                AIRDROP_PLATFORM_V0_1.save(
+                    deps.storage,
+                    &AirdropPlatformV0_1 {
+                        admin: info.sender,
+                        max_match_size: Uint64::from(7_u64),
+                        fee_per_batch: Uint128::from(3_u128),
+                    },
+                )?;
+                Map::<String, AirdropCampaignV0_1>::new("all_campaigns").save(
+                    deps.storage,
+                    String::from(CAMPAIGN_ID),
+                    &campaign,
+                )?;
+            }
+            cw2::set_contract_version(deps.storage, msg.contract, msg.version)?;
+            OPERATORS_V0_3.save(deps.storage, Addr::unchecked(OPERATOR), &true)?;
+            OPERATORS_V0_3.save(deps.storage, Addr::unchecked(WINNER_1), &false)?;
+            for campaign_id in [CAMPAIGN_ID, REMOVED_CAMPAIGN_ID] {
+                LEGACY_CLAIMS.save(
+                    deps.storage,
+                    (String::from(campaign_id), Addr::unchecked(WINNER_1), 0),
+                    &true,
+                )?;
+            }
+            Ok(Response::new())
+        }
+
+        fn assert_operators_migrated(blockchain: &App, airdrop_address: &Addr) {
+            for (account, roles) in [(OPERATOR, vec![Role::AirdropExecutor]), (WINNER_1, vec![])] {
+                let response: AccountRolesResponse = blockchain
+                    .wrap()
+                    .query_wasm_smart(
+                        airdrop_address.clone(),
+                        &QueryMsg::GetAccountRoles {
+                            account: String::from(account),
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(response.roles, roles);
+            }
+            let raw_operator = blockchain
+                .wrap()
+                .query_wasm_raw(
+                    airdrop_address.clone(),
+                    [
+                        &[0, 9][..],
+                        b"operators",
+                        Addr::unchecked(OPERATOR).as_bytes(),
+                    ]
+                    .concat(),
+                )
+                .unwrap();
+            assert_eq!(raw_operator, None);
+        }
+
+        /// Fees of legacy campaigns were withdrawable at any time, so nothing is refundable and
+        /// the unswept balance of the contract is earned
+        fn assert_fees_migrated(blockchain: &App, airdrop_address: &Addr) {
+            let fees: FeeBalancesResponse = blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address.clone(), &QueryMsg::GetFeeBalances {})
+                .unwrap();
+            assert_eq!(
+                fees,
+                FeeBalancesResponse {
+                    fee_balances: vec![FeeAssetBalance {
+                        fee_asset: String::from(NATIVE_DENOM),
+                        earned: Uint128::from(2_u128),
+                        refundable: Uint128::zero(),
+                    }],
+                    escrowed_native: vec![],
+                }
+            );
+            let campaign = get_campaign(blockchain, airdrop_address);
+            assert_eq!(campaign.earned_fee, campaign.airdrop_fee);
+        }
+
+        /// Claims recorded by recipient and asset index still count, unless their campaign is gone
+        fn assert_claims_migrated(blockchain: &App, airdrop_address: &Addr) {
+            for (campaign_id, claimed) in [(CAMPAIGN_ID, true), (REMOVED_CAMPAIGN_ID, false)] {
+                let is_claimed: bool = blockchain
+                    .wrap()
+                    .query_wasm_smart(
+                        airdrop_address.clone(),
+                        &QueryMsg::IsClaimed {
+                            campaign_id: String::from(campaign_id),
+                            recipient: String::from(WINNER_1),
+                            asset_index: Uint64::zero(),
+                            amount: Uint128::from(100_u128),
+                        },
+                    )
+                    .unwrap();
+                assert_eq!(is_claimed, claimed);
+            }
+        }
+
+        fn legacy_execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+            Err(StdError::generic_err("legacy contract"))
+        }
+
+        fn legacy_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+            Err(StdError::generic_err("legacy contract"))
+        }
+
+        fn legacy_blockchain(contract: &str, version: &str) -> (App, Addr, u64) {
+            let mut blockchain = mock_blockchain();
+            let legacy_id = blockchain.store_code(Box::new(ContractWrapper::new(
+                legacy_execute,
+                legacy_instantiate,
+                legacy_query,
+            )));
+            let airdrop_id = blockchain.store_code(Box::new(
+                ContractWrapper::new(
+                    crate::contract::execute,
+                    crate::contract::instantiate,
+                    crate::contract::query,
+                )
+                .with_reply(crate::contract::reply)
+                .with_migrate(crate::contract::migrate),
+            ));
+            let airdrop_address = blockchain
+                .instantiate_contract(
+                    legacy_id,
+                    Addr::unchecked(ADMIN),
+                    &LegacyInstantiateMsg {
+                        contract: String::from(contract),
+                        version: String::from(version),
+                    },
+                    &coins(2, NATIVE_DENOM),
+                    "playlink_airdrop",
+                    Some(String::from(ADMIN)),
+                )
+                .unwrap();
+            (blockchain, airdrop_address, airdrop_id)
+        }
+
+        #[test]
+        fn migrate_from_v0_1_test() {
+            let (mut blockchain, airdrop_address, airdrop_id) =
+                legacy_blockchain("crates.io:playlink-airdrop", "0.1.0");
+            blockchain
+                .migrate_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &MigrateMsg {},
+                    airdrop_id,
+                )
+                .unwrap();
+
+            /* ================= The platform is stored in the new layout ================= */
+            let raw_platform = blockchain
+                .wrap()
+                .query_wasm_raw(airdrop_address.clone(), b"airdrop_platform".to_vec())
+                .unwrap()
+                .unwrap();
+            let platform: AirdropPlatform = from_slice(&raw_platform).unwrap();
+            assert_eq!(platform.admin, Some(Addr::unchecked(ADMIN)));
+            assert_eq!(platform.max_batch_size.u64(), 7);
+            assert_eq!(platform.fee_per_batch.u128(), 3);
+            let version = cw2::query_contract_info(&blockchain, airdrop_address.clone()).unwrap();
+            assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+            /* ================= Old campaigns and settings keep working ================= */
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(campaign.total_available_assets.u128(), 100);
+            assert_eq!(campaign.merkle_root, None);
+            assert!(!campaign.escrow);
+            let by_creator: CampaignsResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::CampaignsByCreator {
+                        creator: String::from(CAMPAIGN_CREATOR),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(by_creator.campaigns, vec![campaign]);
+            let fee: Uint128 = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::EstimateAirdropFee {
+                        num_assets: Uint64::from(8_u64),
+                    },
+                )
+                .unwrap();
+            assert_eq!(fee.u128(), 6);
+
+            /* ================= Operators become airdrop executors ================= */
+            assert_operators_migrated(&blockchain, &airdrop_address);
+            assert_fees_migrated(&blockchain, &airdrop_address);
+            assert_claims_migrated(&blockchain, &airdrop_address);
+            set_up_operator(&mut blockchain, &airdrop_address);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address,
+                    &ExecuteMsg::SetMaxBatchSize {
+                        new_size: Uint64::from(3_u64),
+                    },
+                    &[],
+                )
+                .unwrap();
+        }
+
+        #[test]
+        fn migrate_from_v0_3_test() {
+            let (mut blockchain, airdrop_address, airdrop_id) =
+                legacy_blockchain("crates.io:playlink-airdrop", "0.3.0");
+            blockchain
+                .migrate_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &MigrateMsg {},
+                    airdrop_id,
+                )
+                .unwrap();
+            let version = cw2::query_contract_info(&blockchain, airdrop_address.clone()).unwrap();
+            assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+            /* ================= Steps added after 0.3.0 still run ================= */
+            assert_operators_migrated(&blockchain, &airdrop_address);
+            assert_fees_migrated(&blockchain, &airdrop_address);
+            assert_claims_migrated(&blockchain, &airdrop_address);
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(campaign.total_available_assets.u128(), 100);
+            let by_creator: CampaignsResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::CampaignsByCreator {
+                        creator: String::from(CAMPAIGN_CREATOR),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(by_creator.campaigns, vec![campaign]);
+
+            // The migrated operator passes the role check and reaches the campaign checks
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![],
+                        recipients: vec![],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::CampaignNotStarts {
+                    campaign_id: String::from(CAMPAIGN_ID)
+                }
+            );
+
+            /* ================= Cancelling an old campaign refunds no fee ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let creator_balance = blockchain
+                .wrap()
+                .query_balance(CAMPAIGN_CREATOR, NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(creator_balance.amount.u128(), 1000);
+            let airdrop_balance = blockchain
+                .wrap()
+                .query_balance(airdrop_address.as_str(), NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(airdrop_balance.amount.u128(), 2);
+        }
+
+        #[test]
+        fn update_migrated_campaign_test() {
+            let (mut blockchain, airdrop_address, airdrop_id) =
+                legacy_blockchain("crates.io:playlink-airdrop", "0.3.0");
+            blockchain
+                .migrate_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &MigrateMsg {},
+                    airdrop_id,
+                )
+                .unwrap();
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            let update = |asset_count: usize| {
+                ExecuteMsg::UpdateCampaign(CampaignSettings {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    assets: vec![campaign.assets[0].clone(); asset_count],
+                    starting_time: campaign.starting_time,
+                    merkle_root: None,
+                    escrow: None,
+                    ending_time: None,
+                    strict: None,
+                    self_serve: None,
+                    unique_recipients: None,
+                })
+            };
+
+            /* ================= A higher fee only charges the difference ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &update(8),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            let updated = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(updated.airdrop_fee.u128(), 6);
+            assert_eq!(updated.earned_fee.u128(), 3);
+
+            /* ================= A lower fee never refunds the earned part ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &update(1),
+                    &[],
+                )
+                .unwrap();
+            assert_fees_migrated(&blockchain, &airdrop_address);
+
+            /* ================= The updated campaign can still be cancelled ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let creator_balance = blockchain
+                .wrap()
+                .query_balance(CAMPAIGN_CREATOR, NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(creator_balance.amount.u128(), 1000);
+            let airdrop_balance = blockchain
+                .wrap()
+                .query_balance(airdrop_address.as_str(), NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(airdrop_balance.amount.u128(), 2);
+        }
+
+        #[test]
+        fn migrate_rejects_downgrade_and_foreign_contract_test() {
+            let (mut blockchain, airdrop_address, airdrop_id) =
+                legacy_blockchain("crates.io:playlink-airdrop", "99.0.0");
+            let err = blockchain
+                .migrate_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address,
+                    &MigrateMsg {},
+                    airdrop_id,
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::CannotDowngrade {
+                    from: String::from("99.0.0"),
+                    to: String::from(env!("CARGO_PKG_VERSION")),
+                }
+            );
+
+            let (mut blockchain, airdrop_address, airdrop_id) =
+                legacy_blockchain("crates.io:cw20-base", "0.1.0");
+            let err = blockchain
+                .migrate_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address,
+                    &MigrateMsg {},
+                    airdrop_id,
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::InvalidContractName {
+                    name: String::from("crates.io:cw20-base"),
+                }
+            );
+        }
+    }
+
+    mod admin_transfer {
+        use super::*;
+
+        #[test]
+        fn two_step_admin_transfer_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            let grant_executor = ExecuteMsg::GrantRole {
+                role: Role::AirdropExecutor,
+                account: String::from(OPERATOR),
+            };
+
+            /* ================= Only the admin can propose ================= */
+            let expiry = Uint64::from(blockchain.block_info().time.seconds() + 60);
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::ProposeNewAdmin {
+                        new_admin: String::from(OPERATOR),
+                        expiry: None,
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::NotAdmin {
+                    account: String::from(OPERATOR)
+                }
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::ProposeNewAdmin {
+                        new_admin: String::from(WINNER_1),
+                        expiry: Some(expiry),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let pending_admin: Option<PendingAdmin> = blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address.clone(), &QueryMsg::GetPendingAdmin {})
+                .unwrap();
+            assert_eq!(
+                pending_admin,
+                Some(PendingAdmin {
+                    new_admin: Addr::unchecked(WINNER_1),
+                    expiry: Some(expiry),
+                })
+            );
+
+            /* ================= Only the proposed account can accept, before expiry ================= */
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_2),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::AcceptAdmin {},
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::NotPendingAdmin {
+                    account: String::from(WINNER_2)
+                }
+            );
+            fast_forward(&mut blockchain, 60);
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_1),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::AcceptAdmin {},
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::AdminProposalExpired {}
+            );
+
+            /* ================= Propose again and accept ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::ProposeNewAdmin {
+                        new_admin: String::from(WINNER_1),
+                        expiry: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_1),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::AcceptAdmin {},
+                    &[],
+                )
+                .unwrap();
+            let pending_admin: Option<PendingAdmin> = blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address.clone(), &QueryMsg::GetPendingAdmin {})
+                .unwrap();
+            assert_eq!(pending_admin, None);
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    ADMIN,
+                    &grant_executor,
+                    &[]
+                )
+                .err(),
+                Some(PlaylinkAirdropErr::NotAdmin {
+                    account: String::from(ADMIN)
+                })
+            );
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    WINNER_1,
+                    &grant_executor,
+                    &[]
+                )
+                .err(),
+                None
+            );
+
+            /* ================= Renounce leaves the platform without admin ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_1),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::RenounceAdmin {},
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    WINNER_1,
+                    &grant_executor,
+                    &[]
+                )
+                .err(),
+                Some(PlaylinkAirdropErr::NotAdmin {
+                    account: String::from(WINNER_1)
+                })
+            );
+        }
+    }
+
+    mod list_campaigns {
+        use super::*;
+
+        fn list_campaigns(
+            blockchain: &App,
+            airdrop_address: &Addr,
+            start_after: Option<&str>,
+            limit: Option<u32>,
+            status: Option<CampaignStatus>,
+        ) -> Vec<String> {
+            let response: CampaignsResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::ListCampaigns {
+                        start_after: start_after.map(String::from),
+                        limit,
+                        status,
+                    },
+                )
+                .unwrap();
+            response
+                .campaigns
+                .into_iter()
+                .map(|campaign| campaign.campaign_id)
+                .collect()
+        }
+
+        fn create_native_campaign(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            creator: &str,
+            campaign_id: &str,
+            starting_time: u64,
+            amount: u128,
+        ) {
+            let assets = if amount > 0 {
+                vec![Asset {
+                    asset_type: AssetType::Native,
+                    asset_address: Addr::unchecked(""),
+                    asset_id: String::from(NATIVE_DENOM),
+                    available_amount: Uint128::from(amount),
+                    vesting: None,
+                }]
+            } else {
+                vec![]
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(creator),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(campaign_id),
+                        assets,
+                        starting_time: Uint64::from(starting_time),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(amount + 3, NATIVE_DENOM),
+                )
+                .unwrap();
+        }
+
+        #[test]
+        fn list_campaigns_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+
+            let now = blockchain.block_info().time.seconds();
+            let campaigns = [
+                ("campaign_a", now + 60, 10_u128),
+                ("campaign_b", now + 600, 10_u128),
+                ("campaign_c", now + 60, 0_u128),
+            ];
+            for (campaign_id, starting_time, amount) in campaigns.iter() {
+                create_native_campaign(
+                    &mut blockchain,
+                    &airdrop_address,
+                    CAMPAIGN_CREATOR,
+                    campaign_id,
+                    *starting_time,
+                    *amount,
+                );
+            }
+
+            /* ================= Pagination ================= */
+            assert_eq!(
+                list_campaigns(&blockchain, &airdrop_address, None, Some(2), None),
+                vec!["campaign_a", "campaign_b"]
+            );
+            assert_eq!(
+                list_campaigns(
+                    &blockchain,
+                    &airdrop_address,
+                    Some("campaign_b"),
+                    Some(2),
+                    None
+                ),
+                vec!["campaign_c"]
+            );
+            assert_eq!(
+                list_campaigns(&blockchain, &airdrop_address, None, Some(1000), None).len(),
+                3
+            );
+
+            /* ================= Status filter ================= */
+            assert_eq!(
+                list_campaigns(
+                    &blockchain,
+                    &airdrop_address,
+                    None,
+                    None,
+                    Some(CampaignStatus::Upcoming)
+                ),
+                vec!["campaign_a", "campaign_b", "campaign_c"]
+            );
+            fast_forward(&mut blockchain, 120);
+            assert_eq!(
+                list_campaigns(
+                    &blockchain,
+                    &airdrop_address,
+                    None,
+                    None,
+                    Some(CampaignStatus::Active)
+                ),
+                vec!["campaign_a", "campaign_c"]
+            );
+            assert_eq!(
+                list_campaigns(
+                    &blockchain,
+                    &airdrop_address,
+                    None,
+                    Some(1),
+                    Some(CampaignStatus::Upcoming)
+                ),
+                vec!["campaign_b"]
+            );
+
+            /* ================= Pages point to the next one ================= */
+            let next_start_after = |start_after: Option<&str>, status| {
+                blockchain
+                    .wrap()
+                    .query_wasm_smart::<CampaignsResponse>(
+                        airdrop_address.clone(),
+                        &QueryMsg::ListCampaigns {
+                            start_after: start_after.map(String::from),
+                            limit: Some(1),
+                            status,
+                        },
+                    )
+                    .unwrap()
+                    .next_start_after
+            };
+            assert_eq!(
+                next_start_after(None, Some(CampaignStatus::Active)),
+                Some(String::from("campaign_a"))
+            );
+            assert_eq!(
+                next_start_after(Some("campaign_a"), Some(CampaignStatus::Upcoming)),
+                Some(String::from("campaign_b"))
+            );
+            assert_eq!(
+                next_start_after(Some("campaign_b"), Some(CampaignStatus::Upcoming)),
+                None
+            );
+        }
+
+        #[test]
+        fn campaigns_by_creator_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+
+            let starting_time = blockchain.block_info().time.seconds() + 60;
+            for (creator, campaign_id) in [
+                (CAMPAIGN_CREATOR, "campaign_a"),
+                (ADMIN, "campaign_b"),
+                (CAMPAIGN_CREATOR, "campaign_c"),
+                (CAMPAIGN_CREATOR, "campaign_d"),
+            ] {
+                create_native_campaign(
+                    &mut blockchain,
+                    &airdrop_address,
+                    creator,
+                    campaign_id,
+                    starting_time,
+                    10,
+                );
+            }
+            let campaigns_by_creator =
+                |blockchain: &App, creator: &str, start_after: Option<&str>| {
+                    let response: CampaignsResponse = blockchain
+                        .wrap()
+                        .query_wasm_smart(
+                            airdrop_address.clone(),
+                            &QueryMsg::CampaignsByCreator {
+                                creator: String::from(creator),
+                                start_after: start_after.map(String::from),
+                                limit: Some(2),
+                            },
+                        )
+                        .unwrap();
+                    response
+                        .campaigns
+                        .into_iter()
+                        .map(|campaign| campaign.campaign_id)
+                        .collect::<Vec<_>>()
+                };
+
+            /* ================= Pagination within one creator ================= */
+            assert_eq!(
+                campaigns_by_creator(&blockchain, CAMPAIGN_CREATOR, None),
+                vec!["campaign_a", "campaign_c"]
+            );
+            assert_eq!(
+                campaigns_by_creator(&blockchain, CAMPAIGN_CREATOR, Some("campaign_c")),
+                vec!["campaign_d"]
+            );
+            assert_eq!(
+                campaigns_by_creator(&blockchain, ADMIN, None),
+                vec!["campaign_b"]
+            );
+
+            /* ================= Removed campaigns leave the index ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from("campaign_c"),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                campaigns_by_creator(&blockchain, CAMPAIGN_CREATOR, None),
+                vec!["campaign_a", "campaign_d"]
+            );
+        }
+    }
+
+    mod roles {
+        use super::*;
+        use crate::msg::{AccountRolesResponse, RoleMembersResponse};
+
+        fn list_role_members(
+            blockchain: &App,
+            airdrop_address: &Addr,
+            role: Role,
+            start_after: Option<&str>,
+            limit: Option<u32>,
+        ) -> Vec<Addr> {
+            let response: RoleMembersResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::ListRoleMembers {
+                        role,
+                        start_after: start_after.map(String::from),
+                        limit,
+                    },
+                )
+                .unwrap();
+            response.members
+        }
+
+        #[test]
+        fn grant_and_revoke_roles_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            let set_max_batch_size = ExecuteMsg::SetMaxBatchSize {
+                new_size: Uint64::from(5_u64),
+            };
+            let set_fee_per_batch = ExecuteMsg::SetFeePerBatch {
+                new_fee: Uint128::from(5_u128),
+            };
+
+            /* ================= Only the admin grants roles ================= */
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    &ExecuteMsg::GrantRole {
+                        role: Role::FeeManager,
+                        account: String::from(OPERATOR),
+                    },
+                    &[]
+                )
+                .err(),
+                Some(PlaylinkAirdropErr::NotAdmin {
+                    account: String::from(OPERATOR)
+                })
+            );
+
+            /* ================= Each role only unlocks its own handlers ================= */
+            grant_role(
+                &mut blockchain,
+                &airdrop_address,
+                Role::BatchSizeManager,
+                OPERATOR,
+            );
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    &set_max_batch_size,
+                    &[]
+                )
+                .err(),
+                None
+            );
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    &set_fee_per_batch,
+                    &[]
+                )
+                .err(),
+                Some(PlaylinkAirdropErr::MissingRole {
+                    account: String::from(OPERATOR),
+                    role: Role::FeeManager,
+                })
+            );
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    ADMIN,
+                    &set_fee_per_batch,
+                    &[]
+                )
+                .err(),
+                None
+            );
+
+            /* ================= Roles are enumerable ================= */
+            grant_role(
+                &mut blockchain,
+                &airdrop_address,
+                Role::FeeManager,
+                OPERATOR,
+            );
+            grant_role(
+                &mut blockchain,
+                &airdrop_address,
+                Role::BatchSizeManager,
+                WINNER_1,
+            );
+            grant_role(
+                &mut blockchain,
+                &airdrop_address,
+                Role::BatchSizeManager,
+                WINNER_2,
+            );
+            assert_eq!(
+                list_role_members(
+                    &blockchain,
+                    &airdrop_address,
+                    Role::BatchSizeManager,
+                    None,
+                    Some(2)
+                ),
+                vec![Addr::unchecked(OPERATOR), Addr::unchecked(WINNER_1)]
+            );
+            assert_eq!(
+                list_role_members(
+                    &blockchain,
+                    &airdrop_address,
+                    Role::BatchSizeManager,
+                    Some(WINNER_1),
+                    None
+                ),
+                vec![Addr::unchecked(WINNER_2)]
+            );
+            let response: AccountRolesResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::GetAccountRoles {
+                        account: String::from(OPERATOR),
+                    },
+                )
+                .unwrap();
+            assert_eq!(
+                response.roles,
+                vec![Role::FeeManager, Role::BatchSizeManager]
+            );
+
+            /* ================= Revoked roles are deleted ================= */
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    ADMIN,
+                    &ExecuteMsg::RevokeRole {
+                        role: Role::BatchSizeManager,
+                        account: String::from(WINNER_1),
+                    },
+                    &[]
+                )
+                .err(),
+                None
+            );
+            assert_eq!(
+                list_role_members(
+                    &blockchain,
+                    &airdrop_address,
+                    Role::BatchSizeManager,
+                    None,
+                    None
+                ),
+                vec![Addr::unchecked(OPERATOR), Addr::unchecked(WINNER_2)]
+            );
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    WINNER_1,
+                    &set_max_batch_size,
+                    &[]
+                )
+                .err(),
+                Some(PlaylinkAirdropErr::MissingRole {
+                    account: String::from(WINNER_1),
+                    role: Role::BatchSizeManager,
+                })
+            );
+
+            /* ================= Treasurers withdraw fees ================= */
+            let withdraw = ExecuteMsg::WithdrawAirdropFee {
+                recipient: String::from(WINNER_2),
+            };
+            assert_eq!(
+                execute_as(&mut blockchain, &airdrop_address, WINNER_2, &withdraw, &[]).err(),
+                Some(PlaylinkAirdropErr::MissingRole {
+                    account: String::from(WINNER_2),
+                    role: Role::Treasurer,
+                })
+            );
+            grant_role(&mut blockchain, &airdrop_address, Role::Treasurer, WINNER_2);
+            assert_eq!(
+                execute_as(&mut blockchain, &airdrop_address, WINNER_2, &withdraw, &[]).err(),
+                None
+            );
+        }
+    }
+
+    mod platform_config {
+        use super::*;
+        use crate::{msg::PlatformConfigResponse, state::AirdropPlatform};
+
+        #[test]
+        fn get_platform_config_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            let config: PlatformConfigResponse = blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address.clone(), &QueryMsg::GetPlatformConfig {})
+                .unwrap();
+            assert_eq!(
+                config.platform,
+                AirdropPlatform {
+                    admin: Some(Addr::unchecked(ADMIN)),
+                    max_batch_size: Uint64::from(7_u64),
+                    fee_per_batch: Uint128::from(3_u128),
+                    fee_token: None,
+                }
+            );
+            assert_eq!(
+                config.contract_version.contract,
+                "crates.io:playlink-airdrop"
+            );
+            assert_eq!(config.contract_version.version, env!("CARGO_PKG_VERSION"));
+
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::SetFeePerBatch {
+                        new_fee: Uint128::from(5_u128),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let config: PlatformConfigResponse = blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address, &QueryMsg::GetPlatformConfig {})
+                .unwrap();
+            assert_eq!(config.platform.fee_per_batch.u128(), 5);
+        }
+    }
+
+    mod campaign_end {
+        use super::*;
+
+        fn close_campaign(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            sender: &str,
+        ) -> Option<PlaylinkAirdropErr> {
+            execute_as(
+                blockchain,
+                airdrop_address,
+                sender,
+                &ExecuteMsg::CloseCampaign {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                },
+                &[],
+            )
+            .err()
+        }
+
+        #[test]
+        fn close_ended_campaign_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            /* ================= Ending time must follow starting time ================= */
+            let starting_time = blockchain.block_info().time.seconds() + 60;
+            let mut assets = vec![Asset {
+                asset_type: AssetType::CW20,
+                asset_address: cw20_address.clone(),
+                asset_id: String::from(""),
+                available_amount: Uint128::from(100_u128),
+                vesting: None,
+            }];
+            for _ in 0..7 {
+                assets.push(Asset {
+                    asset_type: AssetType::Native,
+                    asset_address: Addr::unchecked(""),
+                    asset_id: String::from(NATIVE_DENOM),
+                    available_amount: Uint128::from(10_u128),
+                    vesting: None,
+                });
+            }
+            let create_msg = |ending_time: u64| {
+                ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    assets: assets.clone(),
+                    starting_time: Uint64::from(starting_time),
+                    merkle_root: None,
+                    escrow: Some(true),
+                    ending_time: Some(Uint64::from(ending_time)),
+                    strict: None,
+                    self_serve: None,
+                    unique_recipients: None,
+                })
+            };
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &create_msg(starting_time),
+                    &coins(76, NATIVE_DENOM),
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::InvalidEndingTime {}
+            );
+
+            /* ================= Create and fund a campaign ending after 10 minutes ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &create_msg(starting_time + 600),
+                    &coins(76, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::Send {
+                        contract: airdrop_address.clone().into(),
+                        amount: Uint128::from(100_u128),
+                        msg: to_binary(&ReceiveMsg::FundCampaign {
+                            campaign_id: String::from(CAMPAIGN_ID),
+                        })
+                        .unwrap(),
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Run one of the two paid batches ================= */
+            fast_forward(&mut blockchain, 60);
+            let airdrop_msg = ExecuteMsg::Airdrop {
+                campaign_id: String::from(CAMPAIGN_ID),
+                asset_indexes: vec![Uint64::zero()],
+                recipients: vec![String::from(WINNER_1)],
+                amounts: Some(vec![Uint128::from(40_u128)]),
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &airdrop_msg,
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                close_campaign(&mut blockchain, &airdrop_address, CAMPAIGN_CREATOR),
+                Some(PlaylinkAirdropErr::CampaignNotEnded {
+                    campaign_id: String::from(CAMPAIGN_ID)
+                })
+            );
+
+            /* ================= Ended campaigns cannot pay out ================= */
+            fast_forward(&mut blockchain, 600);
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &airdrop_msg,
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::CampaignEnded {
+                    campaign_id: String::from(CAMPAIGN_ID)
+                }
+            );
+            let ended: crate::msg::CampaignsResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::ListCampaigns {
+                        start_after: None,
+                        limit: None,
+                        status: Some(CampaignStatus::Ended),
+                    },
+                )
+                .unwrap();
+            assert_eq!(ended.campaigns.len(), 1);
+
+            /* ================= Closing returns leftovers and the unused fee ================= */
+            assert_eq!(
+                close_campaign(&mut blockchain, &airdrop_address, WINNER_1),
+                Some(PlaylinkAirdropErr::NotCampaignCreator {
+                    campaign_creator: String::from(CAMPAIGN_CREATOR)
+                })
+            );
+            assert_eq!(
+                close_campaign(&mut blockchain, &airdrop_address, CAMPAIGN_CREATOR),
+                None
+            );
+            assert_eq!(
+                blockchain
+                    .wrap()
+                    .query_balance(CAMPAIGN_CREATOR, NATIVE_DENOM)
+                    .unwrap()
+                    .amount
+                    .u128(),
+                1000 - 76 + 70 + 3
+            );
+            assert_eq!(
+                cw20_balance(&blockchain, &cw20_address, CAMPAIGN_CREATOR),
+                960
+            );
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 40);
+            assert_eq!(
+                blockchain
+                    .wrap()
+                    .query_balance(airdrop_address.clone(), NATIVE_DENOM)
+                    .unwrap()
+                    .amount
+                    .u128(),
+                3
+            );
+            assert!(blockchain
+                .wrap()
+                .query_wasm_smart::<AirdropCampaign>(
+                    airdrop_address,
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .is_err());
+        }
+    }
+
+    mod vesting {
+        use super::*;
+        use crate::{helpers::VestingSchedule, msg::VestingStatus};
+
+        fn withdraw_vested(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            recipient: &str,
+        ) -> Result<(), PlaylinkAirdropErr> {
+            execute_as(
+                blockchain,
+                airdrop_address,
+                recipient,
+                &ExecuteMsg::WithdrawVested {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    asset_index: Uint64::zero(),
+                },
+                &[],
+            )
+            .map(|_| ())
+        }
+
+        fn vesting_status(blockchain: &App, airdrop_address: &Addr) -> VestingStatus {
+            blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::GetVestingAllocation {
+                        recipient: String::from(WINNER_1),
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index: Uint64::zero(),
+                    },
+                )
+                .unwrap()
+        }
+
+        #[test]
+        fn linear_vesting_test() {
+            let (mut blockchain, cw20_address, cw721_address, _, airdrop_address) =
+                proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            let starting_time = blockchain.block_info().time.seconds() + 60;
+            let schedule = VestingSchedule {
+                start_time: Uint64::from(starting_time),
+                cliff_time: Uint64::from(starting_time + 100),
+                end_time: Uint64::from(starting_time + 1000),
+            };
+            let create_msg = |asset: Asset| {
+                ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    assets: vec![asset],
+                    starting_time: Uint64::from(starting_time),
+                    merkle_root: None,
+                    escrow: None,
+                    ending_time: None,
+                    strict: None,
+                    self_serve: None,
+                    unique_recipients: None,
+                })
+            };
+
+            /* ================= Only fungible assets can vest ================= */
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &create_msg(Asset {
+                        asset_type: AssetType::CW721,
+                        asset_address: cw721_address,
+                        asset_id: String::from("8888"),
+                        available_amount: Uint128::one(),
+                        vesting: Some(schedule.clone()),
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::VestingNotSupported {
+                    asset_type: AssetType::CW721
+                }
+            );
+
+            /* ================= Airdropped tokens are locked in the contract ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &create_msg(Asset {
+                        asset_type: AssetType::CW20,
+                        asset_address: cw20_address.clone(),
+                        asset_id: String::from(""),
+                        available_amount: Uint128::from(100_u128),
+                        vesting: Some(schedule.clone()),
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(100_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero()],
+                        recipients: vec![String::from(WINNER_1)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 0);
+            assert_eq!(
+                cw20_balance(&blockchain, &cw20_address, airdrop_address.as_str()),
+                100
+            );
+            let status = vesting_status(&blockchain, &airdrop_address);
+            assert_eq!(status.allocation.total_amount.u128(), 100);
+            assert_eq!(status.allocation.schedule, schedule);
+            assert_eq!(status.vested_amount, Uint128::zero());
+
+            /* ================= Nothing unlocks before the cliff ================= */
+            assert_eq!(
+                withdraw_vested(&mut blockchain, &airdrop_address, WINNER_1),
+                Err(PlaylinkAirdropErr::NothingToWithdraw {})
+            );
+            assert_eq!(
+                withdraw_vested(&mut blockchain, &airdrop_address, WINNER_2),
+                Err(PlaylinkAirdropErr::NothingToWithdraw {})
+            );
+
+            /* ================= Tokens unlock linearly ================= */
+            fast_forward(&mut blockchain, 500);
+            assert_eq!(
+                vesting_status(&blockchain, &airdrop_address)
+                    .withdrawable_amount
+                    .u128(),
+                50
+            );
+            withdraw_vested(&mut blockchain, &airdrop_address, WINNER_1).unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 50);
+            assert_eq!(
+                withdraw_vested(&mut blockchain, &airdrop_address, WINNER_1),
+                Err(PlaylinkAirdropErr::NothingToWithdraw {})
+            );
+
+            fast_forward(&mut blockchain, 1000);
+            withdraw_vested(&mut blockchain, &airdrop_address, WINNER_1).unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 100);
+            let allocations: crate::msg::VestingAllocationsResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address,
+                    &QueryMsg::ListVestingAllocations {
+                        recipient: String::from(WINNER_1),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(allocations.allocations.len(), 1);
+            assert_eq!(
+                allocations.allocations[0]
+                    .allocation
+                    .withdrawn_amount
+                    .u128(),
+                100
+            );
+            assert_eq!(
+                allocations.allocations[0].withdrawable_amount,
+                Uint128::zero()
+            );
+        }
+    }
+
+    mod failed_transfers {
+        use super::*;
+        use crate::{msg::FailedTransfersResponse, state::FailedTransfer};
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::{
+            Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, WasmMsg,
+        };
+        use cw_storage_plus::Item;
+
+        const NESTED_CAMPAIGN_ID: &str = "01BX5ZZKBKACTAV9WEVGEMMVRZ";
+
+        #[cw_serde]
+        struct HookTokenInstantiateMsg {
+            airdrop: String,
+        }
+
+        /// `TransferFrom` of a CW20 token, the only message the hook token handles
+        #[cw_serde]
+        enum HookTokenMsg {
+            TransferFrom {
+                owner: String,
+                recipient: String,
+                amount: Uint128,
+            },
+        }
+
+        const HOOK_AIRDROP: Item<String> = Item::new("airdrop");
+
+        fn hook_instantiate(
+            deps: DepsMut,
+            _: Env,
+            _: MessageInfo,
+            msg: HookTokenInstantiateMsg,
+        ) -> StdResult<Response> {
+            HOOK_AIRDROP.save(deps.storage, &msg.airdrop)?;
+            Ok(Response::new())
+        }
+
+        /// Stands in for a token whose transfers airdrop from another campaign, as a delegate
+        /// or self-serve creator contract can
+        fn hook_execute(
+            deps: Deps,
+            _: Env,
+            _: MessageInfo,
+            msg: HookTokenMsg,
+        ) -> StdResult<Response> {
+            let HookTokenMsg::TransferFrom { recipient, .. } = msg;
+            Ok(Response::new().add_message(WasmMsg::Execute {
+                contract_addr: HOOK_AIRDROP.load(deps.storage)?,
+                msg: to_binary(&ExecuteMsg::Airdrop {
+                    campaign_id: String::from(NESTED_CAMPAIGN_ID),
+                    asset_indexes: vec![Uint64::zero()],
+                    recipients: vec![recipient],
+                    amounts: None,
+                })?,
+                funds: vec![],
+            }))
+        }
+
+        fn hook_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+            Err(StdError::generic_err("hook token"))
+        }
+
+        fn list_failed_transfers(blockchain: &App, airdrop_address: &Addr) -> Vec<FailedTransfer> {
+            let response: FailedTransfersResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::ListFailedTransfers {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            response.failed_transfers
+        }
+
+        #[test]
+        fn failed_transfer_is_recorded_and_retried_test() {
+            let (mut blockchain, cw20_address, cw721_address, _, airdrop_address) =
+                proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            /* ================= Create a campaign without approving the NFT ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw721_address.clone(),
+                    &cw721_base::msg::ExecuteMsg::<cw721_base::Extension, Empty>::Mint(
+                        cw721_base::MintMsg::<cw721_base::Extension> {
+                            token_id: String::from("8888"),
+                            owner: String::from(CAMPAIGN_CREATOR),
+                            token_uri: None,
+                            extension: None,
+                        },
+                    ),
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: cw20_address.clone(),
+                                asset_id: String::from(""),
+                                available_amount: Uint128::from(100_u128),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::CW721,
+                                asset_address: cw721_address.clone(),
+                                asset_id: String::from("8888"),
+                                available_amount: Uint128::one(),
+                                vesting: None,
+                            },
+                        ],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(100_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+
+            /* ================= The failing entry does not revert the batch ================= */
+            let airdrop_msg = ExecuteMsg::Airdrop {
+                campaign_id: String::from(CAMPAIGN_ID),
+                asset_indexes: vec![Uint64::zero(), Uint64::one()],
+                recipients: vec![String::from(WINNER_1), String::from(WINNER_2)],
+                amounts: None,
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &airdrop_msg,
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 100);
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(campaign.total_available_assets, Uint128::one());
+            assert_eq!(campaign.assets[0].available_amount, Uint128::zero());
+            assert_eq!(campaign.assets[1].available_amount, Uint128::one());
+            let failed_transfers = list_failed_transfers(&blockchain, &airdrop_address);
+            assert_eq!(failed_transfers.len(), 1);
+            assert_eq!(failed_transfers[0].asset_index, Uint64::one());
+            assert_eq!(failed_transfers[0].recipient, Addr::unchecked(WINNER_2));
+            assert_eq!(failed_transfers[0].amount, Uint128::one());
+
+            /* ================= Retrying clears the failure ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw721_address.clone(),
+                    &cw721::Cw721ExecuteMsg::Approve {
+                        spender: airdrop_address.clone().into(),
+                        token_id: String::from("8888"),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::one()],
+                        recipients: vec![String::from(WINNER_2)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let owner: cw721::OwnerOfResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    cw721_address,
+                    &cw721::Cw721QueryMsg::OwnerOf {
+                        token_id: String::from("8888"),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(owner.owner, WINNER_2);
+            assert!(list_failed_transfers(&blockchain, &airdrop_address).is_empty());
+            assert!(blockchain
+                .wrap()
+                .query_wasm_smart::<AirdropCampaign>(
+                    airdrop_address,
+                    &QueryMsg::GetCampaignById {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                )
+                .is_err());
+        }
+
+        #[test]
+        fn split_failed_transfers_add_up_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![Asset {
+                            asset_type: AssetType::CW20,
+                            asset_address: cw20_address.clone(),
+                            asset_id: String::from(""),
+                            available_amount: Uint128::from(100_u128),
+                            vesting: None,
+                        }],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+            let airdrop = |blockchain: &mut App, amounts: Vec<u128>| {
+                blockchain
+                    .execute_contract(
+                        Addr::unchecked(OPERATOR),
+                        airdrop_address.clone(),
+                        &ExecuteMsg::Airdrop {
+                            campaign_id: String::from(CAMPAIGN_ID),
+                            asset_indexes: vec![Uint64::zero(); amounts.len()],
+                            recipients: vec![String::from(WINNER_1); amounts.len()],
+                            amounts: Some(amounts.into_iter().map(Uint128::from).collect()),
+                        },
+                        &[],
+                    )
+                    .unwrap();
+            };
+
+            /* ================= Failed entries to the same recipient add up ================= */
+            airdrop(&mut blockchain, vec![10, 20]);
+            let failed_transfers = list_failed_transfers(&blockchain, &airdrop_address);
+            assert_eq!(failed_transfers.len(), 1);
+            assert_eq!(failed_transfers[0].amount.u128(), 30);
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(campaign.total_available_assets.u128(), 100);
+
+            /* ================= A retry only deducts what it sends ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(100_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            airdrop(&mut blockchain, vec![10]);
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 10);
+            let failed_transfers = list_failed_transfers(&blockchain, &airdrop_address);
+            assert_eq!(failed_transfers.len(), 1);
+            assert_eq!(failed_transfers[0].amount.u128(), 20);
+            airdrop(&mut blockchain, vec![20]);
+            assert!(list_failed_transfers(&blockchain, &airdrop_address).is_empty());
+        }
+
+        #[test]
+        fn nested_batch_does_not_clobber_replies_test() {
+            let (mut blockchain, _, cw721_address, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            let hook_id = blockchain.store_code(Box::new(ContractWrapper::new(
+                |deps: DepsMut, env, info, msg| hook_execute(deps.as_ref(), env, info, msg),
+                hook_instantiate,
+                hook_query,
+            )));
+            let hook_address = blockchain
+                .instantiate_contract(
+                    hook_id,
+                    Addr::unchecked(ADMIN),
+                    &HookTokenInstantiateMsg {
+                        airdrop: airdrop_address.clone().into(),
+                    },
+                    &[],
+                    "hook_token",
+                    None,
+                )
+                .unwrap();
+
+            /* ================= The hook token airdrops from a native campaign ================= */
+            let starting_time = Uint64::from(blockchain.block_info().time.seconds() + 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(NESTED_CAMPAIGN_ID),
+                        assets: vec![Asset {
+                            asset_type: AssetType::Native,
+                            asset_address: Addr::unchecked(""),
+                            asset_id: String::from(NATIVE_DENOM),
+                            available_amount: Uint128::from(10_u128),
+                            vesting: None,
+                        }],
+                        starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(13, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::SetCampaignDelegates {
+                        campaign_id: String::from(NESTED_CAMPAIGN_ID),
+                        delegates: vec![hook_address.clone().into()],
+                        is_delegates: vec![true],
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= An outer batch pays the hook token, then fails ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw721_address.clone(),
+                    &cw721_base::msg::ExecuteMsg::<cw721_base::Extension, Empty>::Mint(
+                        cw721_base::MintMsg::<cw721_base::Extension> {
+                            token_id: String::from("8888"),
+                            owner: String::from(CAMPAIGN_CREATOR),
+                            token_uri: None,
+                            extension: None,
+                        },
+                    ),
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: hook_address,
+                                asset_id: String::from(""),
+                                available_amount: Uint128::one(),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::CW721,
+                                asset_address: cw721_address,
+                                asset_id: String::from("8888"),
+                                available_amount: Uint128::one(),
+                                vesting: None,
+                            },
+                        ],
+                        starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::one()],
+                        recipients: vec![String::from(WINNER_1), String::from(WINNER_2)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Each batch handled its own replies ================= */
+            let winner_balance = blockchain
+                .wrap()
+                .query_balance(WINNER_1, NATIVE_DENOM)
+                .unwrap();
+            assert_eq!(winner_balance.amount.u128(), 10);
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(campaign.total_available_assets, Uint128::one());
+            assert_eq!(campaign.assets[1].available_amount, Uint128::one());
+            let failed_transfers = list_failed_transfers(&blockchain, &airdrop_address);
+            assert_eq!(failed_transfers.len(), 1);
+            assert_eq!(failed_transfers[0].recipient, Addr::unchecked(WINNER_2));
+            for batch_id in 1..=2_u64 {
+                let raw_batch = blockchain
+                    .wrap()
+                    .query_wasm_raw(
+                        airdrop_address.clone(),
+                        [&[0, 15][..], b"airdrop_batches", &batch_id.to_be_bytes()].concat(),
+                    )
+                    .unwrap();
+                assert_eq!(raw_batch, None);
+            }
+
+            /* ================= Removing the campaign clears its failed transfers ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert!(list_failed_transfers(&blockchain, &airdrop_address).is_empty());
+        }
+    }
+
+    mod pause {
+        use super::*;
+        use crate::msg::PlatformConfigResponse;
+
+        #[test]
+        fn pause_platform_and_campaign_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            let create_msg = ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                campaign_id: String::from(CAMPAIGN_ID),
+                assets: vec![Asset {
+                    asset_type: AssetType::Native,
+                    asset_address: Addr::unchecked(""),
+                    asset_id: String::from(NATIVE_DENOM),
+                    available_amount: Uint128::from(10_u128),
+                    vesting: None,
+                }],
+                starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                merkle_root: None,
+                escrow: None,
+                ending_time: None,
+                strict: None,
+                self_serve: None,
+                unique_recipients: None,
+            });
+            let airdrop_msg = ExecuteMsg::Airdrop {
+                campaign_id: String::from(CAMPAIGN_ID),
+                asset_indexes: vec![Uint64::zero()],
+                recipients: vec![String::from(WINNER_1)],
+                amounts: Some(vec![Uint128::from(5_u128)]),
+            };
+
+            /* ================= Only the admin and pausers can pause the platform ================= */
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    &ExecuteMsg::SetPaused { paused: true },
+                    &[]
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::MissingRole {
+                    account: String::from(OPERATOR),
+                    role: Role::Pauser,
+                }
+            );
+            let response = execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                ADMIN,
+                &ExecuteMsg::SetPaused { paused: true },
+                &[],
+            )
+            .unwrap();
+            let event = response
+                .events
+                .iter()
+                .find(|event| event.ty == "wasm-playlink_airdrop")
+                .unwrap();
+            assert!(event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "action" && attr.value == "pause"));
+            let config: PlatformConfigResponse = blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address.clone(), &QueryMsg::GetPlatformConfig {})
+                .unwrap();
+            assert!(config.paused);
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    CAMPAIGN_CREATOR,
+                    &create_msg,
+                    &coins(13, NATIVE_DENOM)
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::PlatformPaused {}
+            );
+            execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                ADMIN,
+                &ExecuteMsg::SetPaused { paused: false },
+                &[],
+            )
+            .unwrap();
+            execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                CAMPAIGN_CREATOR,
+                &create_msg,
+                &coins(13, NATIVE_DENOM),
+            )
+            .unwrap();
+            fast_forward(&mut blockchain, 60);
+
+            /* ================= A global pause blocks airdrops, claims and withdrawals ================= */
+            execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                ADMIN,
+                &ExecuteMsg::SetPaused { paused: true },
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    &airdrop_msg,
+                    &[]
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::PlatformPaused {}
+            );
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    WINNER_1,
+                    &ExecuteMsg::Claim {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index: Uint64::zero(),
+                        amount: Uint128::from(5_u128),
+                        proof: vec![],
+                    },
+                    &[]
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::PlatformPaused {}
+            );
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    WINNER_1,
+                    &ExecuteMsg::WithdrawVested {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index: Uint64::zero(),
+                    },
+                    &[]
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::PlatformPaused {}
+            );
+            execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                ADMIN,
+                &ExecuteMsg::SetPaused { paused: false },
+                &[],
+            )
+            .unwrap();
+
+            /* ================= Campaigns can be paused by the creator or the admin ================= */
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    &ExecuteMsg::SetCampaignPaused {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        paused: true,
+                    },
+                    &[]
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::NotCampaignCreator {
+                    campaign_creator: String::from(CAMPAIGN_CREATOR)
+                }
+            );
+            execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                CAMPAIGN_CREATOR,
+                &ExecuteMsg::SetCampaignPaused {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    paused: true,
+                },
+                &[],
+            )
+            .unwrap();
+            assert!(get_campaign(&blockchain, &airdrop_address).paused);
+            assert_eq!(
+                execute_as(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    &airdrop_msg,
+                    &[]
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::CampaignPaused {
+                    campaign_id: String::from(CAMPAIGN_ID)
+                }
+            );
+            execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                ADMIN,
+                &ExecuteMsg::SetCampaignPaused {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    paused: false,
+                },
+                &[],
+            )
+            .unwrap();
+            execute_as(
+                &mut blockchain,
+                &airdrop_address,
+                OPERATOR,
+                &airdrop_msg,
+                &[],
+            )
+            .unwrap();
+            assert_eq!(
+                get_campaign(&blockchain, &airdrop_address).total_available_assets,
+                Uint128::from(5_u128)
+            );
+        }
+    }
+
+    mod cw20_fee {
+        use super::*;
+
+        fn send_create_campaign(
+            blockchain: &mut App,
+            cw20_address: &Addr,
+            airdrop_address: &Addr,
+            amount: u128,
+        ) -> Option<PlaylinkAirdropErr> {
+            let create_msg = ReceiveMsg::CreateAirdropCampaign(CampaignSettings {
+                campaign_id: String::from(CAMPAIGN_ID),
+                assets: vec![Asset {
+                    asset_type: AssetType::CW20,
+                    asset_address: cw20_address.clone(),
+                    asset_id: String::from(""),
+                    available_amount: Uint128::from(100_u128),
+                    vesting: None,
+                }],
+                starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                merkle_root: None,
+                escrow: None,
+                ending_time: None,
+                strict: None,
+                self_serve: None,
+                unique_recipients: None,
+            });
+            execute_as(
+                blockchain,
+                cw20_address,
+                CAMPAIGN_CREATOR,
+                &cw20::Cw20ExecuteMsg::Send {
+                    contract: airdrop_address.clone().into(),
+                    amount: Uint128::from(amount),
+                    msg: to_binary(&create_msg).unwrap(),
+                },
+                &[],
+            )
+            .err()
+        }
+
+        #[test]
+        fn pay_airdrop_fee_in_cw20_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+
+            /* ================= CW20 fees need a platform fee token ================= */
+            assert_eq!(
+                send_create_campaign(&mut blockchain, &cw20_address, &airdrop_address, 10),
+                Some(PlaylinkAirdropErr::InvalidFeeToken {
+                    token: cw20_address.to_string()
+                })
+            );
+            let set_fee_token = ExecuteMsg::SetFeeToken {
+                fee_token: Some(cw20_address.to_string()),
+            };
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &set_fee_token,
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::MissingRole {
+                    account: String::from(OPERATOR),
+                    role: Role::FeeManager,
+                }
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &set_fee_token,
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Create a campaign in one Send ================= */
+            assert_eq!(
+                send_create_campaign(&mut blockchain, &cw20_address, &airdrop_address, 2),
+                Some(PlaylinkAirdropErr::InsufficientAirdropFee {
+                    fee: Uint128::from(3_u128),
+                    denom: cw20_address.to_string(),
+                })
+            );
+            assert_eq!(
+                send_create_campaign(&mut blockchain, &cw20_address, &airdrop_address, 10),
+                None
+            );
+            assert_eq!(
+                cw20_balance(&blockchain, &cw20_address, CAMPAIGN_CREATOR),
+                997
+            );
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(campaign.airdrop_fee.u128(), 3);
+            assert_eq!(campaign.fee_token, Some(cw20_address.clone()));
+
+            /* ================= Refunds are paid in the fee token ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                cw20_balance(&blockchain, &cw20_address, CAMPAIGN_CREATOR),
+                1000
+            );
+            assert_eq!(
+                blockchain
+                    .wrap()
+                    .query_balance(CAMPAIGN_CREATOR, NATIVE_DENOM)
+                    .unwrap()
+                    .amount
+                    .u128(),
+                1000
+            );
+
+            /* ================= The admin withdraws CW20 fees once earned ================= */
+            assert_eq!(
+                send_create_campaign(&mut blockchain, &cw20_address, &airdrop_address, 3),
+                None
+            );
+            let withdraw_msg = ExecuteMsg::WithdrawAirdropFee {
+                recipient: String::from(ADMIN),
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &withdraw_msg,
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, ADMIN), 0);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(100_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            fast_forward(&mut blockchain, 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero()],
+                        recipients: vec![String::from(WINNER_1)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &withdraw_msg,
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, ADMIN), 3);
+            assert_eq!(
+                cw20_balance(&blockchain, &cw20_address, airdrop_address.as_str()),
+                0
+            );
+        }
+    }
+
+    mod fee_ledger {
+        use super::*;
+        use crate::msg::{FeeAssetBalance, FeeBalancesResponse};
+
+        fn fee_balances(blockchain: &App, airdrop_address: &Addr) -> FeeBalancesResponse {
+            blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address.clone(), &QueryMsg::GetFeeBalances {})
+                .unwrap()
+        }
+
+        fn native_fees(earned: u128, refundable: u128) -> Vec<FeeAssetBalance> {
+            vec![FeeAssetBalance {
+                fee_asset: String::from(NATIVE_DENOM),
+                earned: Uint128::from(earned),
+                refundable: Uint128::from(refundable),
+            }]
+        }
+
+        fn native_balance(blockchain: &App, address: &str) -> u128 {
+            blockchain
+                .wrap()
+                .query_balance(address, NATIVE_DENOM)
+                .unwrap()
+                .amount
+                .u128()
+        }
+
+        #[test]
+        fn fees_are_earned_per_batch_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::SetMaxBatchSize {
+                        new_size: Uint64::one(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let native_asset = Asset {
+                asset_type: AssetType::Native,
+                asset_address: Addr::unchecked(""),
+                asset_id: String::from(NATIVE_DENOM),
+                available_amount: Uint128::from(10_u128),
+                vesting: None,
+            };
+
+            /* ================= The fee is refundable until its batches run ================= */
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset.clone(); 3],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(39, NATIVE_DENOM),
+                )
+                .unwrap();
+            assert_eq!(
+                fee_balances(&blockchain, &airdrop_address),
+                FeeBalancesResponse {
+                    fee_balances: native_fees(0, 9),
+                    escrowed_native: coins(30, NATIVE_DENOM),
+                }
+            );
+
+            /* ================= Lowering the fee refunds the overpayment ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::UpdateCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset.clone(); 2],
+                        starting_time: campaign_starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(native_balance(&blockchain, CAMPAIGN_CREATOR), 974);
+            assert_eq!(
+                fee_balances(&blockchain, &airdrop_address).fee_balances,
+                native_fees(0, 6)
+            );
+
+            /* ================= Withdrawals are capped to earned fees ================= */
+            fast_forward(&mut blockchain, 30 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero()],
+                        recipients: vec![String::from(WINNER_1)],
+                        amounts: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                fee_balances(&blockchain, &airdrop_address).fee_balances,
+                native_fees(3, 3)
+            );
+            let withdraw_msg = ExecuteMsg::WithdrawAirdropFee {
+                recipient: String::from(ADMIN),
+            };
+            for _ in 0..2 {
+                blockchain
+                    .execute_contract(
+                        Addr::unchecked(ADMIN),
+                        airdrop_address.clone(),
+                        &withdraw_msg,
+                        &[],
+                    )
+                    .unwrap();
+            }
+            assert_eq!(native_balance(&blockchain, ADMIN), 1003);
+            assert_eq!(native_balance(&blockchain, airdrop_address.as_str()), 13);
+
+            /* ================= Cancelling refunds the unearned fee ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(native_balance(&blockchain, CAMPAIGN_CREATOR), 987);
+            assert_eq!(native_balance(&blockchain, airdrop_address.as_str()), 0);
+            assert_eq!(
+                fee_balances(&blockchain, &airdrop_address),
+                FeeBalancesResponse {
+                    fee_balances: native_fees(0, 0),
+                    escrowed_native: vec![],
+                }
+            );
+        }
+
+        #[test]
+        fn claims_earn_fees_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+
+            /* ================= A single-leaf campaign paying 60 of its 100 assets ================= */
+            let leaf = merkle_leaf(WINNER_1, Uint64::zero(), Uint128::from(60_u128));
+            let campaign_starting_time =
+                Uint64::from(blockchain.block_info().time.seconds() + 20 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![Asset {
+                            asset_type: AssetType::Native,
+                            asset_address: Addr::unchecked(""),
+                            asset_id: String::from(NATIVE_DENOM),
+                            available_amount: Uint128::from(100_u128),
+                            vesting: None,
+                        }],
+                        starting_time: campaign_starting_time,
+                        merkle_root: Some(hex::encode(leaf)),
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(103, NATIVE_DENOM),
+                )
+                .unwrap();
+
+            /* ================= Claims earn the fee pro rata ================= */
+            fast_forward(&mut blockchain, 30 * 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(WINNER_1),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Claim {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index: Uint64::zero(),
+                        amount: Uint128::from(60_u128),
+                        proof: vec![],
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                fee_balances(&blockchain, &airdrop_address).fee_balances,
+                native_fees(1, 2)
+            );
+
+            /* ================= Cancelling refunds only the unearned fee ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(native_balance(&blockchain, CAMPAIGN_CREATOR), 939);
+            assert_eq!(native_balance(&blockchain, airdrop_address.as_str()), 1);
+            assert_eq!(
+                fee_balances(&blockchain, &airdrop_address),
+                FeeBalancesResponse {
+                    fee_balances: native_fees(1, 0),
+                    escrowed_native: vec![],
+                }
+            );
+        }
+    }
+
+    mod simulate_airdrop {
+        use super::*;
+        use crate::msg::{SimulateAirdropResponse, SimulatedTransfer};
+
+        fn simulate(
+            blockchain: &App,
+            airdrop_address: &Addr,
+            asset_indexes: Vec<u64>,
+            recipients: Vec<&str>,
+            amounts: Vec<u128>,
+        ) -> SimulateAirdropResponse {
+            blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::SimulateAirdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: asset_indexes.into_iter().map(Uint64::from).collect(),
+                        recipients: recipients.into_iter().map(String::from).collect(),
+                        amounts: Some(amounts.into_iter().map(Uint128::from).collect()),
+                    },
+                )
+                .unwrap()
+        }
+
+        fn entry(
+            asset_index: u64,
+            recipient: &str,
+            amount: u128,
+            error: Option<PlaylinkAirdropErr>,
+        ) -> SimulatedTransfer {
+            SimulatedTransfer {
+                asset_index: Uint64::from(asset_index),
+                recipient: String::from(recipient),
+                amount: Uint128::from(amount),
+                error: error.map(|err| err.to_string()),
+            }
+        }
+
+        #[test]
+        fn simulate_airdrop_test() {
+            let (mut blockchain, cw20_address, cw721_address, cw1155_address, airdrop_address) =
+                proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            /* ================= Create a campaign the creator cannot fully cover ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw721_address.clone(),
+                    &cw721_base::msg::ExecuteMsg::<cw721_base::Extension, Empty>::Mint(
+                        cw721_base::MintMsg::<cw721_base::Extension> {
+                            token_id: String::from("8888"),
+                            owner: String::from(CAMPAIGN_CREATOR),
+                            token_uri: None,
+                            extension: None,
+                        },
+                    ),
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw1155_address.clone(),
+                    &cw1155::Cw1155ExecuteMsg::Mint {
+                        to: String::from(CAMPAIGN_CREATOR),
+                        token_id: String::from("1234"),
+                        value: Uint128::from(10_u128),
+                        msg: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: cw20_address.clone(),
+                                asset_id: String::from(""),
+                                available_amount: Uint128::from(100_u128),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::CW721,
+                                asset_address: cw721_address.clone(),
+                                asset_id: String::from("8888"),
+                                available_amount: Uint128::one(),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::CW1155,
+                                asset_address: cw1155_address.clone(),
+                                asset_id: String::from("1234"),
+                                available_amount: Uint128::from(15_u128),
+                                vesting: None,
+                            },
+                        ],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+
+            /* ================= Batch-level checks reject the whole batch ================= */
+            assert_eq!(
+                simulate(
+                    &blockchain,
+                    &airdrop_address,
+                    vec![0],
+                    vec![WINNER_1],
+                    vec![10]
+                ),
+                SimulateAirdropResponse {
+                    error: Some(
+                        PlaylinkAirdropErr::CampaignNotStarts {
+                            campaign_id: String::from(CAMPAIGN_ID)
+                        }
+                        .to_string()
+                    ),
+                    entries: vec![],
+                }
+            );
+
+            /* ================= Each entry reports why it would fail ================= */
+            fast_forward(&mut blockchain, 60);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(50_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw1155_address.clone(),
+                    &cw1155::Cw1155ExecuteMsg::ApproveAll {
+                        operator: airdrop_address.clone().into(),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let report = simulate(
+                &blockchain,
+                &airdrop_address,
+                vec![0, 0, 1, 2, 0, 3],
+                vec![WINNER_1, WINNER_2, WINNER_1, WINNER_2, WINNER_1, WINNER_2],
+                vec![40, 20, 1, 15, 0, 1],
+            );
+            assert_eq!(report.error, None);
+            assert_eq!(
+                report.entries,
+                vec![
+                    entry(0, WINNER_1, 40, None),
+                    entry(
+                        0,
+                        WINNER_2,
+                        20,
+                        Some(PlaylinkAirdropErr::InsufficientAllowance {
+                            index: Uint64::zero(),
+                            allowance: Uint128::from(50_u128),
+                        })
+                    ),
+                    entry(
+                        1,
+                        WINNER_1,
+                        1,
+                        Some(PlaylinkAirdropErr::AssetNotApproved {
+                            index: Uint64::one()
+                        })
+                    ),
+                    entry(
+                        2,
+                        WINNER_2,
+                        15,
+                        Some(PlaylinkAirdropErr::InsufficientAssetBalance {
+                            index: Uint64::from(2_u64),
+                            balance: Uint128::from(10_u128),
+                        })
+                    ),
+                    entry(
+                        0,
+                        WINNER_1,
+                        0,
+                        Some(PlaylinkAirdropErr::InvalidAssetAmount {
+                            asset_amount: Uint128::zero()
+                        })
+                    ),
+                    entry(
+                        3,
+                        WINNER_2,
+                        1,
+                        Some(PlaylinkAirdropErr::IndexOutOfBound {
+                            index: Uint64::from(3_u64)
+                        })
+                    ),
+                ]
+            );
+            assert_eq!(
+                get_campaign(&blockchain, &airdrop_address).total_available_assets,
+                Uint128::from(116_u128)
+            );
+
+            /* ================= A clean report matches the executed batch ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw721_address.clone(),
+                    &cw721::Cw721ExecuteMsg::Approve {
+                        spender: airdrop_address.clone().into(),
+                        token_id: String::from("8888"),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let report = simulate(
+                &blockchain,
+                &airdrop_address,
+                vec![0, 1, 2],
+                vec![WINNER_1, WINNER_1, WINNER_2],
+                vec![50, 1, 10],
+            );
+            assert!(report.entries.iter().all(|entry| entry.error.is_none()));
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::one(), Uint64::from(2_u64)],
+                        recipients: vec![
+                            String::from(WINNER_1),
+                            String::from(WINNER_1),
+                            String::from(WINNER_2),
+                        ],
+                        amounts: Some(vec![
+                            Uint128::from(50_u128),
+                            Uint128::one(),
+                            Uint128::from(10_u128),
+                        ]),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(cw20_balance(&blockchain, &cw20_address, WINNER_1), 50);
+            assert_eq!(
+                get_campaign(&blockchain, &airdrop_address).total_available_assets,
+                Uint128::from(55_u128)
+            );
+        }
+    }
+
+    mod strict_validation {
+        use super::*;
+
+        #[test]
+        fn strict_campaign_validation_test() {
+            let (mut blockchain, cw20_address, cw721_address, cw1155_address, airdrop_address) =
+                proper_instantiate();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw721_address.clone(),
+                    &cw721_base::msg::ExecuteMsg::<cw721_base::Extension, Empty>::Mint(
+                        cw721_base::MintMsg::<cw721_base::Extension> {
+                            token_id: String::from("8888"),
+                            owner: String::from(CAMPAIGN_CREATOR),
+                            token_uri: None,
+                            extension: None,
+                        },
+                    ),
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw1155_address.clone(),
+                    &cw1155::Cw1155ExecuteMsg::Mint {
+                        to: String::from(CAMPAIGN_CREATOR),
+                        token_id: String::from("1234"),
+                        value: Uint128::from(10_u128),
+                        msg: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            let starting_time = Uint64::from(blockchain.block_info().time.seconds() + 60);
+            let cw20_asset = Asset {
+                asset_type: AssetType::CW20,
+                asset_address: cw20_address.clone(),
+                asset_id: String::from(""),
+                available_amount: Uint128::from(600_u128),
+                vesting: None,
+            };
+            let cw721_asset = |token_id: &str| Asset {
+                asset_type: AssetType::CW721,
+                asset_address: cw721_address.clone(),
+                asset_id: String::from(token_id),
+                available_amount: Uint128::one(),
+                vesting: None,
+            };
+            let cw1155_asset = |amount: u128| Asset {
+                asset_type: AssetType::CW1155,
+                asset_address: cw1155_address.clone(),
+                asset_id: String::from("1234"),
+                available_amount: Uint128::from(amount),
+                vesting: None,
+            };
+            let create = |blockchain: &mut App, assets: Vec<Asset>, escrow: bool| {
+                execute_as(
+                    blockchain,
+                    &airdrop_address,
+                    CAMPAIGN_CREATOR,
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets,
+                        starting_time,
+                        merkle_root: None,
+                        escrow: Some(escrow),
+                        ending_time: None,
+                        strict: Some(true),
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+            };
+
+            /* ================= Unfulfillable assets are rejected by index ================= */
+            assert_eq!(
+                create(&mut blockchain, vec![cw20_asset.clone()], false).unwrap_err(),
+                PlaylinkAirdropErr::InsufficientAllowance {
+                    index: Uint64::zero(),
+                    allowance: Uint128::zero(),
+                }
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(1000_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                create(
+                    &mut blockchain,
+                    vec![cw20_asset.clone(), cw20_asset.clone()],
+                    false
+                )
+                .unwrap_err(),
+                PlaylinkAirdropErr::InsufficientAssetBalance {
+                    index: Uint64::one(),
+                    balance: Uint128::from(1000_u128),
+                }
+            );
+            assert!(matches!(
+                create(&mut blockchain, vec![cw721_asset("9999")], false).unwrap_err(),
+                PlaylinkAirdropErr::AssetQueryFailed { index, .. } if index.is_zero()
+            ));
+            assert_eq!(
+                create(&mut blockchain, vec![cw721_asset("8888")], false).unwrap_err(),
+                PlaylinkAirdropErr::AssetNotApproved {
+                    index: Uint64::zero()
+                }
+            );
+            assert_eq!(
+                create(&mut blockchain, vec![cw1155_asset(15)], true).unwrap_err(),
+                PlaylinkAirdropErr::InsufficientAssetBalance {
+                    index: Uint64::zero(),
+                    balance: Uint128::from(10_u128),
+                }
+            );
+
+            /* ================= Escrow campaigns need no approval ================= */
+            create(
+                &mut blockchain,
+                vec![cw20_asset, cw721_asset("8888"), cw1155_asset(10)],
+                true,
+            )
+            .unwrap();
+
+            /* ================= Updates are validated the same way ================= */
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::UpdateCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![cw1155_asset(10)],
+                        starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: Some(true),
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::AssetNotApproved {
+                    index: Uint64::zero()
+                }
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address,
+                    &ExecuteMsg::UpdateCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![cw1155_asset(10)],
+                        starting_time,
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &[],
+                )
+                .unwrap();
+        }
+
+        #[test]
+        fn cw721_operator_approval_test() {
+            let (mut blockchain, _, cw721_address, _, airdrop_address) = proper_instantiate();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    cw721_address.clone(),
+                    &cw721_base::msg::ExecuteMsg::<cw721_base::Extension, Empty>::Mint(
+                        cw721_base::MintMsg::<cw721_base::Extension> {
+                            token_id: String::from("8888"),
+                            owner: String::from(CAMPAIGN_CREATOR),
+                            token_uri: None,
+                            extension: None,
+                        },
+                    ),
+                    &[],
+                )
+                .unwrap();
+            let create_msg = ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                campaign_id: String::from(CAMPAIGN_ID),
+                assets: vec![Asset {
+                    asset_type: AssetType::CW721,
+                    asset_address: cw721_address.clone(),
+                    asset_id: String::from("8888"),
+                    available_amount: Uint128::one(),
+                    vesting: None,
+                }],
+                starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                merkle_root: None,
+                escrow: None,
+                ending_time: None,
+                strict: Some(true),
+                self_serve: None,
+                unique_recipients: None,
+            });
+
+            /* ================= Operators of other accounts do not count ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw721_address.clone(),
+                    &cw721::Cw721ExecuteMsg::ApproveAll {
+                        operator: String::from(OPERATOR),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &create_msg,
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::AssetNotApproved {
+                    index: Uint64::zero()
+                }
+            );
+
+            /* ================= ApproveAll for this contract is accepted ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw721_address,
+                    &cw721::Cw721ExecuteMsg::ApproveAll {
+                        operator: airdrop_address.clone().into(),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address,
+                    &create_msg,
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+        }
+    }
+
+    mod campaign_delegates {
+        use super::*;
+        use crate::msg::DelegatesResponse;
+
+        fn list_delegates(
+            blockchain: &App,
+            airdrop_address: &Addr,
+            start_after: Option<&str>,
+        ) -> Vec<Addr> {
+            let response: DelegatesResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::ListCampaignDelegates {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        start_after: start_after.map(String::from),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            response.delegates
+        }
+
+        fn set_delegates(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            sender: &str,
+            delegates: Vec<&str>,
+            is_delegates: Vec<bool>,
+        ) -> Option<PlaylinkAirdropErr> {
+            execute_as(
+                blockchain,
+                airdrop_address,
+                sender,
+                &ExecuteMsg::SetCampaignDelegates {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    delegates: delegates.into_iter().map(String::from).collect(),
+                    is_delegates,
+                },
+                &[],
+            )
+            .err()
+        }
+
+        fn airdrop_as(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            sender: &str,
+        ) -> Option<PlaylinkAirdropErr> {
+            execute_as(
+                blockchain,
+                airdrop_address,
+                sender,
+                &ExecuteMsg::Airdrop {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    asset_indexes: vec![Uint64::zero()],
+                    recipients: vec![String::from(WINNER_1)],
+                    amounts: Some(vec![Uint128::from(10_u128)]),
+                },
+                &[],
+            )
+            .err()
+        }
+
+        #[test]
+        fn campaign_delegates_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![Asset {
+                            asset_type: AssetType::Native,
+                            asset_address: Addr::unchecked(""),
+                            asset_id: String::from(NATIVE_DENOM),
+                            available_amount: Uint128::from(100_u128),
+                            vesting: None,
+                        }],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(103, NATIVE_DENOM),
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+
+            /* ================= Only the creator can delegate ================= */
+            assert_eq!(
+                set_delegates(
+                    &mut blockchain,
+                    &airdrop_address,
+                    OPERATOR,
+                    vec![WINNER_1],
+                    vec![true]
+                ),
+                Some(PlaylinkAirdropErr::NotCampaignCreator {
+                    campaign_creator: String::from(CAMPAIGN_CREATOR)
+                })
+            );
+            assert_eq!(
+                airdrop_as(&mut blockchain, &airdrop_address, WINNER_1),
+                Some(PlaylinkAirdropErr::MissingRole {
+                    account: String::from(WINNER_1),
+                    role: Role::AirdropExecutor,
+                })
+            );
+
+            /* ================= Delegates can airdrop this campaign ================= */
+            assert_eq!(
+                set_delegates(
+                    &mut blockchain,
+                    &airdrop_address,
+                    CAMPAIGN_CREATOR,
+                    vec![WINNER_1, WINNER_2],
+                    vec![true, true]
+                ),
+                None
+            );
+            assert_eq!(
+                list_delegates(&blockchain, &airdrop_address, None),
+                vec![Addr::unchecked(WINNER_1), Addr::unchecked(WINNER_2)]
+            );
+            assert_eq!(
+                list_delegates(&blockchain, &airdrop_address, Some(WINNER_1)),
+                vec![Addr::unchecked(WINNER_2)]
+            );
+            assert_eq!(
+                airdrop_as(&mut blockchain, &airdrop_address, WINNER_1),
+                None
+            );
+            assert_eq!(
+                get_campaign(&blockchain, &airdrop_address).total_available_assets,
+                Uint128::from(90_u128)
+            );
+
+            /* ================= Revoked delegates lose access ================= */
+            assert_eq!(
+                set_delegates(
+                    &mut blockchain,
+                    &airdrop_address,
+                    CAMPAIGN_CREATOR,
+                    vec![WINNER_1],
+                    vec![false]
+                ),
+                None
+            );
+            assert_eq!(
+                airdrop_as(&mut blockchain, &airdrop_address, WINNER_1),
+                Some(PlaylinkAirdropErr::MissingRole {
+                    account: String::from(WINNER_1),
+                    role: Role::AirdropExecutor,
+                })
+            );
+            assert_eq!(
+                list_delegates(&blockchain, &airdrop_address, None),
+                vec![Addr::unchecked(WINNER_2)]
+            );
+
+            /* ================= Delegates are dropped with the campaign ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert!(list_delegates(&blockchain, &airdrop_address, None).is_empty());
+        }
+    }
+
+    mod self_serve {
+        use super::*;
+
+        fn create_campaign(blockchain: &mut App, airdrop_address: &Addr, self_serve: bool) {
+            let native_asset = Asset {
+                asset_type: AssetType::Native,
+                asset_address: Addr::unchecked(""),
+                asset_id: String::from(NATIVE_DENOM),
+                available_amount: Uint128::from(10_u128),
+                vesting: None,
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset; 4],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: Some(self_serve),
+                        unique_recipients: None,
+                    }),
+                    &coins(46, NATIVE_DENOM),
+                )
+                .unwrap();
+        }
+
+        fn airdrop_as_creator(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            asset_indexes: Vec<u64>,
+        ) -> Option<PlaylinkAirdropErr> {
+            let recipients = vec![String::from(WINNER_1); asset_indexes.len()];
+            execute_as(
+                blockchain,
+                airdrop_address,
+                CAMPAIGN_CREATOR,
+                &ExecuteMsg::Airdrop {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    asset_indexes: asset_indexes.into_iter().map(Uint64::from).collect(),
+                    recipients,
+                    amounts: None,
+                },
+                &[],
+            )
+            .err()
+        }
+
+        #[test]
+        fn creator_runs_airdrop_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::SetMaxBatchSize {
+                        new_size: Uint64::from(2_u64),
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Creators cannot airdrop by default ================= */
+            create_campaign(&mut blockchain, &airdrop_address, false);
+            fast_forward(&mut blockchain, 60);
+            assert_eq!(
+                airdrop_as_creator(&mut blockchain, &airdrop_address, vec![0]),
+                Some(PlaylinkAirdropErr::MissingRole {
+                    account: String::from(CAMPAIGN_CREATOR),
+                    role: Role::AirdropExecutor,
+                })
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+
+            /* ================= Self-serve creators airdrop under the same limits ================= */
+            create_campaign(&mut blockchain, &airdrop_address, true);
+            assert!(get_campaign(&blockchain, &airdrop_address).self_serve);
+            fast_forward(&mut blockchain, 60);
+            assert_eq!(
+                airdrop_as_creator(&mut blockchain, &airdrop_address, vec![0, 1, 2]),
+                Some(PlaylinkAirdropErr::TooManyAssetsAirdropped {
+                    num_assets: Uint64::from(3_u64)
+                })
+            );
+            assert_eq!(
+                airdrop_as_creator(&mut blockchain, &airdrop_address, vec![0, 1]),
+                None
+            );
+            let fees: crate::msg::FeeBalancesResponse = blockchain
+                .wrap()
+                .query_wasm_smart(airdrop_address.clone(), &QueryMsg::GetFeeBalances {})
+                .unwrap();
+            assert_eq!(fees.fee_balances[0].earned, Uint128::from(3_u128));
+            assert_eq!(fees.fee_balances[0].refundable, Uint128::from(3_u128));
+            assert_eq!(
+                blockchain
+                    .wrap()
+                    .query_balance(WINNER_1, NATIVE_DENOM)
+                    .unwrap()
+                    .amount
+                    .u128(),
+                20
+            );
+        }
+    }
+
+    mod events {
+        use super::*;
+
+        /// Attributes of the `wasm-playlink_airdrop` events of `response` with the given action
+        fn events(response: &AppResponse, action: &str) -> Vec<Vec<(String, String)>> {
+            response
+                .events
+                .iter()
+                .filter(|event| event.ty == "wasm-playlink_airdrop")
+                .map(|event| {
+                    event
+                        .attributes
+                        .iter()
+                        .filter(|attribute| attribute.key != "_contract_addr")
+                        .map(|attribute| (attribute.key.clone(), attribute.value.clone()))
+                        .collect::<Vec<_>>()
+                })
+                .filter(|attributes| {
+                    attributes[0] == (String::from("action"), String::from(action))
+                })
+                .collect()
+        }
+
+        fn attributes(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(key, value)| (String::from(*key), String::from(*value)))
+                .collect()
+        }
+
+        #[test]
+        fn structured_events_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+
+            /* ================= Config changes report the whole platform config ================= */
+            let response = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::SetFeePerBatch {
+                        new_fee: Uint128::from(4_u128),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                events(&response, "set_fee_per_batch"),
+                vec![attributes(&[
+                    ("action", "set_fee_per_batch"),
+                    ("admin", ADMIN),
+                    ("max_batch_size", "7"),
+                    ("fee_per_batch", "4"),
+                ])]
+            );
+
+            /* ================= Campaign creation ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.to_string(),
+                        amount: Uint128::from(100_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let starting_time = blockchain.block_info().time.seconds() + 60;
+            let response = blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: cw20_address.clone(),
+                                asset_id: String::from(""),
+                                available_amount: Uint128::from(100_u128),
+                                vesting: None,
+                            },
+                            Asset {
+                                asset_type: AssetType::Native,
+                                asset_address: Addr::unchecked(""),
+                                asset_id: String::from(NATIVE_DENOM),
+                                available_amount: Uint128::from(10_u128),
+                                vesting: None,
+                            },
+                        ],
+                        starting_time: Uint64::from(starting_time),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(14, NATIVE_DENOM),
+                )
+                .unwrap();
+            assert_eq!(
+                events(&response, "create_airdrop_campaign"),
+                vec![attributes(&[
+                    ("action", "create_airdrop_campaign"),
+                    ("campaign_id", CAMPAIGN_ID),
+                    ("creator", CAMPAIGN_CREATOR),
+                    ("num_assets", "2"),
+                    ("total_available_assets", "110"),
+                    ("max_batch_size", "7"),
+                    ("starting_time", &starting_time.to_string()),
+                    ("airdrop_fee", "4"),
+                    ("fee_denom", NATIVE_DENOM),
+                    ("escrow", "false"),
+                    ("self_serve", "false"),
+                    ("unique_recipients", "false"),
+                ])]
+            );
+
+            /* ================= One event per transfer ================= */
+            fast_forward(&mut blockchain, 60);
+            let response = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::one()],
+                        recipients: vec![String::from(WINNER_1), String::from(WINNER_2)],
+                        amounts: Some(vec![Uint128::from(40_u128), Uint128::from(10_u128)]),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                events(&response, "airdrop"),
+                vec![
+                    attributes(&[
+                        ("action", "airdrop"),
+                        ("campaign_id", CAMPAIGN_ID),
+                        ("asset_index", "0"),
+                        ("asset_type", "CW20"),
+                        ("asset_address", cw20_address.as_str()),
+                        ("amount", "40"),
+                        ("recipient", WINNER_1),
+                        ("vesting", "false"),
+                    ]),
+                    attributes(&[
+                        ("action", "airdrop"),
+                        ("campaign_id", CAMPAIGN_ID),
+                        ("asset_index", "1"),
+                        ("asset_type", "Native"),
+                        ("asset_id", NATIVE_DENOM),
+                        ("amount", "10"),
+                        ("recipient", WINNER_2),
+                        ("vesting", "false"),
+                    ]),
+                ]
+            );
+
+            /* ================= Failed transfers are not reported ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address.clone(),
+                    &cw20::Cw20ExecuteMsg::DecreaseAllowance {
+                        spender: airdrop_address.to_string(),
+                        amount: Uint128::from(60_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            let response = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::Airdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero()],
+                        recipients: vec![String::from(WINNER_1)],
+                        amounts: Some(vec![Uint128::from(10_u128)]),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert!(events(&response, "airdrop").is_empty());
+            assert_eq!(events(&response, "airdrop_transfer_failed").len(), 1);
+
+            /* ================= Fee withdrawal reports each fee asset ================= */
+            let response = blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address,
+                    &ExecuteMsg::WithdrawAirdropFee {
+                        recipient: String::from(ADMIN),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                events(&response, "withdraw_airdrop_fee"),
+                vec![attributes(&[
+                    ("action", "withdraw_airdrop_fee"),
+                    ("recipient", ADMIN),
+                    ("fee_denom", NATIVE_DENOM),
+                    ("amount", "4"),
+                ])]
+            );
+        }
+    }
+
+    mod voucher_claims {
+        use super::*;
+        use crate::{helpers::Voucher, msg::VoucherSignersResponse};
+        use cosmwasm_std::Binary;
+        use k256::{
+            ecdsa::{signature::DigestSigner, Signature, SigningKey, VerifyingKey},
+            elliptic_curve::sec1::ToEncodedPoint,
+        };
+
+        fn signing_key(seed: u8) -> SigningKey {
+            SigningKey::from_bytes(&[seed; 32]).unwrap()
+        }
+
+        fn pubkey(key: &SigningKey) -> Binary {
+            Binary::from(VerifyingKey::from(key).to_bytes().as_slice())
+        }
+
+        fn uncompressed_pubkey(key: &SigningKey) -> Binary {
+            Binary::from(VerifyingKey::from(key).to_encoded_point(false).as_bytes())
+        }
+
+        /// Signs `sha256(contract_address || voucher_json)` like an off-chain signer would
+        fn sign(key: &SigningKey, airdrop_address: &Addr, voucher: &Voucher) -> Binary {
+            let digest = Sha256::new()
+                .chain_update(airdrop_address.as_bytes())
+                .chain_update(cosmwasm_std::to_vec(voucher).unwrap());
+            let signature: Signature = key.sign_digest(digest);
+            Binary::from(signature.as_ref())
+        }
+
+        fn claim_with_voucher(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            voucher: &Voucher,
+            pubkey: Binary,
+            signature: Binary,
+        ) -> Option<PlaylinkAirdropErr> {
+            // Anyone can relay a voucher, the assets go to its recipient
+            execute_as(
+                blockchain,
+                airdrop_address,
+                WINNER_2,
+                &ExecuteMsg::ClaimWithVoucher {
+                    voucher: voucher.clone(),
+                    pubkey,
+                    signature,
+                },
+                &[],
+            )
+            .err()
+        }
+
+        #[test]
+        fn claim_with_voucher_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            let signer = signing_key(1);
+            let native_asset = Asset {
+                asset_type: AssetType::Native,
+                asset_address: Addr::unchecked(""),
+                asset_id: String::from(NATIVE_DENOM),
+                available_amount: Uint128::from(10_u128),
+                vesting: None,
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset; 2],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: None,
+                    }),
+                    &coins(23, NATIVE_DENOM),
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+            let voucher = Voucher {
+                campaign_id: String::from(CAMPAIGN_ID),
+                recipient: String::from(WINNER_1),
+                asset_index: Uint64::zero(),
+                amount: Uint128::from(4_u128),
+                nonce: Uint64::from(7_u64),
+                expiry: Uint64::from(blockchain.block_info().time.seconds() + 60),
+            };
+            let signature = sign(&signer, &airdrop_address, &voucher);
+
+            /* ================= Only the admin registers signers ================= */
+            assert_eq!(
+                claim_with_voucher(
+                    &mut blockchain,
+                    &airdrop_address,
+                    &voucher,
+                    pubkey(&signer),
+                    signature.clone()
+                ),
+                Some(PlaylinkAirdropErr::VoucherSignerNotRegistered {})
+            );
+            let register = ExecuteMsg::RegisterVoucherSigner {
+                pubkey: pubkey(&signer),
+            };
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(OPERATOR),
+                    airdrop_address.clone(),
+                    &register,
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::NotAdmin {
+                    account: String::from(OPERATOR)
+                }
+            );
+            let err = blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::RegisterVoucherSigner {
+                        pubkey: Binary::from(vec![2; 20]),
+                    },
+                    &[],
+                )
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<PlaylinkAirdropErr>().unwrap(),
+                PlaylinkAirdropErr::InvalidVoucherSigner {}
+            );
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &register,
+                    &[],
+                )
+                .unwrap();
+            let signers: VoucherSignersResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::ListVoucherSigners {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(signers.pubkeys, vec![pubkey(&signer)]);
+
+            // Both encodings of a key name the same signer
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::RegisterVoucherSigner {
+                        pubkey: uncompressed_pubkey(&signer),
+                    },
+                    &[],
+                )
+                .unwrap();
+            let signers: VoucherSignersResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::ListVoucherSigners {
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(signers.pubkeys, vec![pubkey(&signer)]);
+
+            /* ================= Tampered and foreign vouchers are rejected ================= */
+            let tampered = Voucher {
+                amount: Uint128::from(10_u128),
+                ..voucher.clone()
+            };
+            assert_eq!(
+                claim_with_voucher(
+                    &mut blockchain,
+                    &airdrop_address,
+                    &tampered,
+                    pubkey(&signer),
+                    signature.clone()
+                ),
+                Some(PlaylinkAirdropErr::InvalidVoucherSignature {})
+            );
+            assert_eq!(
+                claim_with_voucher(
+                    &mut blockchain,
+                    &airdrop_address,
+                    &voucher,
+                    pubkey(&signer),
+                    sign(&signing_key(2), &airdrop_address, &voucher)
+                ),
+                Some(PlaylinkAirdropErr::InvalidVoucherSignature {})
+            );
+
+            /* ================= A voucher is redeemed once ================= */
+            assert_eq!(
+                claim_with_voucher(
+                    &mut blockchain,
+                    &airdrop_address,
+                    &voucher,
+                    pubkey(&signer),
+                    signature.clone()
+                ),
+                None
+            );
+            assert_eq!(
+                blockchain
+                    .wrap()
+                    .query_balance(WINNER_1, NATIVE_DENOM)
+                    .unwrap()
+                    .amount
+                    .u128(),
+                4
+            );
+            assert_eq!(
+                get_campaign(&blockchain, &airdrop_address).assets[0].available_amount,
+                Uint128::from(6_u128)
+            );
+            let used: bool = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::IsVoucherNonceUsed {
+                        pubkey: pubkey(&signer),
+                        nonce: Uint64::from(7_u64),
+                    },
+                )
+                .unwrap();
+            assert!(used);
+            for pubkey in [pubkey(&signer), uncompressed_pubkey(&signer)] {
+                assert_eq!(
+                    claim_with_voucher(
+                        &mut blockchain,
+                        &airdrop_address,
+                        &voucher,
+                        pubkey,
+                        signature.clone()
+                    ),
+                    Some(PlaylinkAirdropErr::VoucherAlreadyUsed {
+                        nonce: Uint64::from(7_u64)
+                    })
+                );
+            }
+
+            /* ================= Expired vouchers are rejected ================= */
+            let next = Voucher {
+                nonce: Uint64::from(8_u64),
+                ..voucher
+            };
+            let next_signature = sign(&signer, &airdrop_address, &next);
+            fast_forward(&mut blockchain, 60);
+            assert_eq!(
+                claim_with_voucher(
+                    &mut blockchain,
+                    &airdrop_address,
+                    &next,
+                    pubkey(&signer),
+                    next_signature
+                ),
+                Some(PlaylinkAirdropErr::VoucherExpired {
+                    nonce: Uint64::from(8_u64)
+                })
+            );
+
+            /* ================= Vouchers of revoked signers are rejected ================= */
+            let last = Voucher {
+                expiry: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                ..next
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(ADMIN),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::RevokeVoucherSigner {
+                        pubkey: uncompressed_pubkey(&signer),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert_eq!(
+                claim_with_voucher(
+                    &mut blockchain,
+                    &airdrop_address,
+                    &last,
+                    pubkey(&signer),
+                    sign(&signer, &airdrop_address, &last)
+                ),
+                Some(PlaylinkAirdropErr::VoucherSignerNotRegistered {})
+            );
+        }
+    }
+
+    mod unique_recipients {
+        use super::*;
+        use crate::msg::SimulateAirdropResponse;
+
+        fn airdrop(
+            blockchain: &mut App,
+            airdrop_address: &Addr,
+            asset_indexes: Vec<u64>,
+            recipients: Vec<&str>,
+        ) -> Option<PlaylinkAirdropErr> {
+            execute_as(
+                blockchain,
+                airdrop_address,
+                OPERATOR,
+                &ExecuteMsg::Airdrop {
+                    campaign_id: String::from(CAMPAIGN_ID),
+                    asset_indexes: asset_indexes.into_iter().map(Uint64::from).collect(),
+                    recipients: recipients.into_iter().map(String::from).collect(),
+                    amounts: None,
+                },
+                &[],
+            )
+            .err()
+        }
+
+        fn has_received(blockchain: &App, airdrop_address: &Addr, recipient: &str) -> bool {
+            blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::HasReceived {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        recipient: String::from(recipient),
+                    },
+                )
+                .unwrap()
+        }
+
+        #[test]
+        fn reject_repeated_recipients_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            let native_asset = Asset {
+                asset_type: AssetType::Native,
+                asset_address: Addr::unchecked(""),
+                asset_id: String::from(NATIVE_DENOM),
+                available_amount: Uint128::from(10_u128),
+                vesting: None,
+            };
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![native_asset; 4],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: Some(true),
+                    }),
+                    &coins(43, NATIVE_DENOM),
+                )
+                .unwrap();
+            assert!(get_campaign(&blockchain, &airdrop_address).unique_recipients);
+            fast_forward(&mut blockchain, 60);
+
+            /* ================= Repeats within a batch are rejected ================= */
+            let recipient_error = || PlaylinkAirdropErr::RecipientAlreadyReceived {
+                recipient: String::from(WINNER_1),
+            };
+            let simulation: SimulateAirdropResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address.clone(),
+                    &QueryMsg::SimulateAirdrop {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_indexes: vec![Uint64::zero(), Uint64::one()],
+                        recipients: vec![String::from(WINNER_1), String::from(WINNER_1)],
+                        amounts: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(simulation.entries[0].error, None);
+            assert_eq!(
+                simulation.entries[1].error,
+                Some(recipient_error().to_string())
+            );
+            assert_eq!(
+                airdrop(
+                    &mut blockchain,
+                    &airdrop_address,
+                    vec![0, 1],
+                    vec![WINNER_1, WINNER_1]
+                ),
+                Some(recipient_error())
+            );
+            assert!(!has_received(&blockchain, &airdrop_address, WINNER_1));
+
+            /* ================= Repeats across batches are rejected ================= */
+            assert_eq!(
+                airdrop(&mut blockchain, &airdrop_address, vec![0], vec![WINNER_1]),
+                None
+            );
+            assert!(has_received(&blockchain, &airdrop_address, WINNER_1));
+            assert!(!has_received(&blockchain, &airdrop_address, WINNER_2));
+            assert_eq!(
+                airdrop(&mut blockchain, &airdrop_address, vec![1], vec![WINNER_1]),
+                Some(recipient_error())
+            );
+            assert_eq!(
+                airdrop(&mut blockchain, &airdrop_address, vec![1], vec![WINNER_2]),
+                None
+            );
+            assert!(has_received(&blockchain, &airdrop_address, WINNER_2));
+            assert_eq!(
+                blockchain
+                    .wrap()
+                    .query_balance(WINNER_1, NATIVE_DENOM)
+                    .unwrap()
+                    .amount
+                    .u128(),
+                10
+            );
+        }
+
+        #[test]
+        fn unique_claims_test() {
+            let (mut blockchain, _, _, _, airdrop_address) = proper_instantiate();
+            let leaf1 = merkle_leaf(WINNER_1, Uint64::zero(), Uint128::from(10_u128));
+            let leaf2 = merkle_leaf(WINNER_1, Uint64::one(), Uint128::from(10_u128));
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::Native,
+                                asset_address: Addr::unchecked(""),
+                                asset_id: String::from(NATIVE_DENOM),
+                                available_amount: Uint128::from(10_u128),
+                                vesting: None,
+                            };
+                            2
+                        ],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: Some(hex::encode(super::merkle_claim::hash_pair(
+                            leaf1, leaf2,
+                        ))),
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: Some(true),
+                    }),
+                    &coins(23, NATIVE_DENOM),
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+
+            /* ================= A recipient with two leaves claims once ================= */
+            let claim = |blockchain: &mut App, asset_index: Uint64, sibling: [u8; 32]| {
+                execute_as(
+                    blockchain,
+                    &airdrop_address,
+                    WINNER_1,
+                    &ExecuteMsg::Claim {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        asset_index,
+                        amount: Uint128::from(10_u128),
+                        proof: vec![hex::encode(sibling)],
+                    },
+                    &[],
+                )
+                .err()
+            };
+            assert_eq!(claim(&mut blockchain, Uint64::zero(), leaf2), None);
+            assert!(has_received(&blockchain, &airdrop_address, WINNER_1));
+            assert_eq!(
+                claim(&mut blockchain, Uint64::one(), leaf1),
+                Some(PlaylinkAirdropErr::RecipientAlreadyReceived {
+                    recipient: String::from(WINNER_1)
+                })
+            );
+
+            /* ================= Recipients are cleared with the campaign ================= */
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CancelCampaign {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert!(!has_received(&blockchain, &airdrop_address, WINNER_1));
+        }
+
+        #[test]
+        fn failed_transfer_keeps_recipients_test() {
+            let (mut blockchain, cw20_address, _, _, airdrop_address) = proper_instantiate();
+            set_up_operator(&mut blockchain, &airdrop_address);
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::CreateAirdropCampaign(CampaignSettings {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        assets: vec![
+                            Asset {
+                                asset_type: AssetType::CW20,
+                                asset_address: cw20_address.clone(),
+                                asset_id: String::from(""),
+                                available_amount: Uint128::from(50_u128),
+                                vesting: None,
+                            };
+                            2
+                        ],
+                        starting_time: Uint64::from(blockchain.block_info().time.seconds() + 60),
+                        merkle_root: None,
+                        escrow: None,
+                        ending_time: None,
+                        strict: None,
+                        self_serve: None,
+                        unique_recipients: Some(true),
+                    }),
+                    &coins(3, NATIVE_DENOM),
+                )
+                .unwrap();
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    airdrop_address.clone(),
+                    &ExecuteMsg::SetCampaignDelegates {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        delegates: vec![String::from(WINNER_2)],
+                        is_delegates: vec![true],
+                    },
+                    &[],
+                )
+                .unwrap();
+            // Only the first transfer of the batch is covered by the allowance
+            blockchain
+                .execute_contract(
+                    Addr::unchecked(CAMPAIGN_CREATOR),
+                    cw20_address,
+                    &cw20::Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: airdrop_address.clone().into(),
+                        amount: Uint128::from(50_u128),
+                        expires: None,
+                    },
+                    &[],
+                )
+                .unwrap();
+            fast_forward(&mut blockchain, 60);
+
+            /* ================= A batch emptying the campaign fails one transfer ================= */
+            assert_eq!(
+                airdrop(
+                    &mut blockchain,
+                    &airdrop_address,
+                    vec![0, 1],
+                    vec![WINNER_1, WINNER_2]
+                ),
+                None
+            );
+            let campaign = get_campaign(&blockchain, &airdrop_address);
+            assert_eq!(campaign.total_available_assets.u128(), 50);
+
+            /* ================= Records of the restored campaign are kept ================= */
+            assert!(has_received(&blockchain, &airdrop_address, WINNER_1));
+            assert!(!has_received(&blockchain, &airdrop_address, WINNER_2));
+            assert_eq!(
+                airdrop(&mut blockchain, &airdrop_address, vec![1], vec![WINNER_1]),
+                Some(PlaylinkAirdropErr::RecipientAlreadyReceived {
+                    recipient: String::from(WINNER_1)
+                })
+            );
+            let delegates: crate::msg::DelegatesResponse = blockchain
+                .wrap()
+                .query_wasm_smart(
+                    airdrop_address,
+                    &QueryMsg::ListCampaignDelegates {
+                        campaign_id: String::from(CAMPAIGN_ID),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(delegates.delegates, vec![Addr::unchecked(WINNER_2)]);
+        }
+    }
 }